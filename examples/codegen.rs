@@ -0,0 +1,14 @@
+use std::env;
+use std::io;
+
+fn main() {
+    let font = bdf::open(env::args().nth(1).expect("missing font file")).unwrap();
+    let name = env::args().nth(2).unwrap_or_else(|| "FONT".into());
+    let mut generator = bdf::Generator::new(&font, name);
+
+    if let Some(subset) = env::args().nth(3) {
+        generator = generator.subset(subset.chars());
+    }
+
+    generator.write(io::stdout()).unwrap();
+}
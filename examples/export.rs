@@ -0,0 +1,24 @@
+use std::env;
+use std::io;
+
+fn main() {
+    let font = bdf::open(env::args().nth(1).expect("missing font file")).unwrap();
+    let format = match env::args().nth(2).as_deref() {
+        Some("gfx") => bdf::Format::Gfx,
+        Some("u8g2") => bdf::Format::U8g2,
+        _ => panic!("expected gfx or u8g2"),
+    };
+    let name = env::args().nth(3).unwrap_or_else(|| "font".into());
+    let mut exporter = bdf::Exporter::new(&font, name, format);
+
+    // Ranges are given as pairs of characters, like `AZaz`.
+    if let Some(ranges) = env::args().nth(4) {
+        let chars = ranges.chars().collect::<Vec<_>>();
+
+        for range in chars.chunks(2) {
+            exporter = exporter.range(range[0]..=*range.last().unwrap());
+        }
+    }
+
+    exporter.write(io::stdout()).unwrap();
+}
@@ -0,0 +1,131 @@
+//! Compile-time BDF font embedding.
+//!
+//! The `include_bdf!` macro reads a BDF font while compiling and expands to a
+//! `bdf::StaticFont`, so the font needs no parsing at runtime.
+//!
+//! ```rust,ignore
+//! static FONT: bdf::StaticFont = bdf_macros::include_bdf!("fonts/gohufont.bdf", chars = "0123456789");
+//! ```
+
+#![warn(missing_docs)]
+
+use std::env;
+use std::fs::File;
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+/// The arguments of `include_bdf!`.
+struct Input {
+    path: LitStr,
+    chars: Option<LitStr>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut chars = None;
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+
+            if name != "chars" {
+                return Err(syn::Error::new(name.span(), "expected `chars`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            chars = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Input { path, chars })
+    }
+}
+
+/// Reads a BDF font at compile time and expands to a `bdf::StaticFont`.
+///
+/// The path is relative to the directory of the manifest of the crate using
+/// the macro, and `chars` optionally limits the glyphs to the given
+/// characters.
+///
+/// Fonts that cannot be read are reported as compile errors, with the line of
+/// the font file the reader failed on.
+///
+/// ```compile_fail
+/// static FONT: bdf::StaticFont = bdf_macros::include_bdf!("tests/malformed.bdf");
+/// ```
+#[proc_macro]
+pub fn include_bdf(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &Input) -> syn::Result<TokenStream2> {
+    let path =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(input.path.value());
+
+    let file = File::open(&path).map_err(|error| {
+        syn::Error::new(
+            input.path.span(),
+            format!("{}: {}", input.path.value(), error),
+        )
+    })?;
+
+    // The reader is kept to point at the line of the font file it failed on.
+    let mut reader = bdf::Reader::from(file);
+    let font = reader.font().map_err(|error| {
+        syn::Error::new(
+            input.path.span(),
+            format!("{}:{}: {}", input.path.value(), reader.line_number(), error),
+        )
+    })?;
+
+    let mut generator = bdf::Generator::new(&font, "").path("::bdf");
+
+    if let Some(chars) = &input.chars {
+        generator = generator.subset(chars.value().chars());
+    }
+
+    let expression = generator
+        .expression()
+        .map_err(|error| {
+            syn::Error::new(
+                input.path.span(),
+                format!("{}: {}", input.path.value(), error),
+            )
+        })?
+        .parse::<TokenStream2>()?;
+    let path = path.to_string_lossy();
+
+    // Depending on the file makes the compiler expand the macro again when the
+    // font changes.
+    Ok(quote! {
+        {
+            const _: &[u8] = include_bytes!(#path);
+            #expression
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, Input};
+
+    #[test]
+    fn malformed_font() {
+        let input = syn::parse_str::<Input>(r#""tests/malformed.bdf""#).unwrap();
+
+        assert_eq!(
+            expand(&input).unwrap_err().to_string(),
+            "tests/malformed.bdf:5: Malformed character definition"
+        );
+    }
+}
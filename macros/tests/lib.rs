@@ -0,0 +1,32 @@
+use bdf_macros::include_bdf;
+
+static GOHUFONT: bdf::StaticFont = include_bdf!("../tests/gohufont.bdf", chars = "Hi,");
+
+#[test]
+fn include_gohufont_font() {
+    let font = bdf::open("../tests/gohufont.bdf").unwrap();
+
+    assert_eq!(GOHUFONT.name, font.name());
+    assert_eq!(GOHUFONT.glyphs.len(), 3);
+    assert_eq!(GOHUFONT.text_width("Hi,"), 18);
+
+    let mut expected = Vec::new();
+    bdf::Renderer::new(&font).draw("Hi,", 0, 0, &mut |x, y| expected.push((x, y)));
+
+    let mut pixels = Vec::new();
+    GOHUFONT.draw("Hi,", 0, 0, &mut |x, y| pixels.push((x, y)));
+
+    expected.sort_unstable();
+    pixels.sort_unstable();
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn include_whole_font() {
+    let font = include_bdf!("../tests/gohufont.bdf");
+
+    assert_eq!(
+        font.glyphs.len(),
+        bdf::open("../tests/gohufont.bdf").unwrap().glyphs().len()
+    );
+}
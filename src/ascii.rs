@@ -0,0 +1,333 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Bitmap, BoundingBox, Error, Glyph};
+
+/// Characters parsed as set bits.
+const ON: &[char] = &['#', '@', '*', 'X', 'x', '1', '█'];
+
+/// Characters parsed as unset bits.
+const OFF: &[char] = &['.', ' ', '-', '_', '+', '0', '░'];
+
+/// ASCII art rendering of a `Bitmap` or `Glyph`.
+///
+/// By default set bits are drawn as `#` and unset bits as `.`, one row per
+/// line, which is also what `Bitmap` parses back.
+#[derive(Clone, Copy, Debug)]
+pub struct Ascii<'a> {
+    map: &'a Bitmap,
+    bounds: Option<BoundingBox>,
+
+    on: &'a str,
+    off: &'a str,
+
+    baseline: Option<&'a str>,
+    origin: Option<&'a str>,
+}
+
+impl<'a> Ascii<'a> {
+    pub(crate) fn new(map: &'a Bitmap, bounds: Option<BoundingBox>) -> Self {
+        Ascii {
+            map,
+            bounds,
+
+            on: "#",
+            off: ".",
+
+            baseline: None,
+            origin: None,
+        }
+    }
+
+    /// Sets the string drawn for set bits.
+    #[inline]
+    pub fn on(mut self, value: &'a str) -> Self {
+        self.on = value;
+        self
+    }
+
+    /// Sets the string drawn for unset bits.
+    #[inline]
+    pub fn off(mut self, value: &'a str) -> Self {
+        self.off = value;
+        self
+    }
+
+    /// Draws unset bits of the row sitting on the baseline with the given
+    /// string, only used for glyphs.
+    #[inline]
+    pub fn baseline(mut self, value: &'a str) -> Self {
+        self.baseline = Some(value);
+        self
+    }
+
+    /// Draws the bit at the origin with the given string when unset, only
+    /// used for glyphs.
+    #[inline]
+    pub fn origin(mut self, value: &'a str) -> Self {
+        self.origin = Some(value);
+        self
+    }
+}
+
+impl<'a> fmt::Display for Ascii<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = self.map;
+        let markers = self.baseline.is_some() || self.origin.is_some();
+
+        let (bbx, area) = match self.bounds {
+            // Make sure the origin is visible when markers are drawn.
+            Some(bbx) if markers => (
+                bbx,
+                bbx.union(&BoundingBox {
+                    width: 1,
+                    height: 1,
+
+                    x: 0,
+                    y: 0,
+                }),
+            ),
+
+            _ => {
+                let bbx = BoundingBox {
+                    width: map.width(),
+                    height: map.height(),
+
+                    x: 0,
+                    y: 0,
+                };
+
+                (bbx, bbx)
+            }
+        };
+
+        for row in 0..area.height as i32 {
+            if row > 0 {
+                f.write_str("\n")?;
+            }
+
+            let y = area.y + area.height as i32 - row - 1;
+
+            for column in 0..area.width as i32 {
+                let x = area.x + column;
+                let (mx, my) = (x - bbx.x, bbx.y + bbx.height as i32 - y - 1);
+
+                let set = mx >= 0
+                    && my >= 0
+                    && mx < map.width() as i32
+                    && my < map.height() as i32
+                    && map.get(mx as u32, my as u32);
+
+                let value = match (set, self.origin, self.baseline) {
+                    (true, _, _) => self.on,
+
+                    (false, Some(origin), _) if x == 0 && y == 0 => origin,
+
+                    (false, _, Some(baseline)) if y == 0 => baseline,
+
+                    _ => self.off,
+                };
+
+                f.write_str(value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Bitmap {
+    /// Creates a configurable ASCII art rendering of the bitmap.
+    #[inline]
+    pub fn ascii(&self) -> Ascii<'_> {
+        Ascii::new(self, None)
+    }
+}
+
+impl fmt::Display for Bitmap {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.ascii().fmt(f)
+    }
+}
+
+impl fmt::Display for Glyph {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.ascii().fmt(f)
+    }
+}
+
+impl FromStr for Bitmap {
+    type Err = Error;
+
+    /// Parses ASCII art, one row per line, padding shorter rows with unset
+    /// bits.
+    ///
+    /// Every character is a bit, including spaces, unless the string starts
+    /// with a newline like an indented multi-line literal. Then the first line
+    /// and a last line only holding whitespace are ignored, and the
+    /// indentation common to the rows is removed.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut rows = string
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number as u32 + 1, line))
+            .collect::<Vec<_>>();
+
+        if string.starts_with('\n') || string.starts_with("\r\n") {
+            rows.remove(0);
+
+            if matches!(rows.last(), Some((_, line)) if line.trim().is_empty()) {
+                rows.pop();
+            }
+
+            let indentation = rows
+                .iter()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(_, line)| line.chars().take_while(|c| c.is_whitespace()).count())
+                .min()
+                .unwrap_or(0);
+
+            for (_, line) in &mut rows {
+                let skipped = line
+                    .char_indices()
+                    .take(indentation)
+                    .map(|(index, c)| index + c.len_utf8())
+                    .last()
+                    .unwrap_or(0);
+
+                *line = &line[skipped..];
+            }
+        }
+
+        let width = rows
+            .iter()
+            .map(|(_, line)| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut map = Bitmap::new(width as u32, rows.len() as u32);
+
+        for (y, &(line_number, line)) in rows.iter().enumerate() {
+            for (x, character) in line.chars().enumerate() {
+                if ON.contains(&character) {
+                    map.set(x as u32, y as u32, true);
+                } else if !OFF.contains(&character) {
+                    return Err(Error::InvalidPixel {
+                        character,
+                        line_number,
+                    });
+                }
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, BoundingBox, Error, Glyph};
+
+    #[test]
+    fn display() {
+        let map: Bitmap = "
+            .#.
+            #.#
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(map.to_string(), ".#.\n#.#");
+        assert_eq!(map.ascii().on("██").off("░░").to_string(), "░░██░░\n██░░██");
+    }
+
+    #[test]
+    fn parse() {
+        let map: Bitmap = "
+            #
+            .#.
+        "
+        .parse()
+        .unwrap();
+
+        let mut expected = Bitmap::new(3, 2);
+        expected.set(0, 0, true);
+        expected.set(1, 1, true);
+
+        assert_eq!(map, expected);
+        assert_eq!("".parse::<Bitmap>().unwrap(), Bitmap::new(0, 0));
+
+        match ".#\n#?".parse::<Bitmap>() {
+            Err(Error::InvalidPixel {
+                character: '?',
+                line_number: 2,
+            }) => (),
+
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_spaces() {
+        let map: Bitmap = "  #\n   \n # ".parse().unwrap();
+
+        let mut expected = Bitmap::new(3, 3);
+        expected.set(2, 0, true);
+        expected.set(1, 2, true);
+
+        assert_eq!(map, expected);
+        assert_eq!(
+            map.ascii().off(" ").to_string().parse::<Bitmap>().unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn parse_indented() {
+        let map: Bitmap = "
+              #
+            ...
+            
+             # 
+        "
+        .parse()
+        .unwrap();
+
+        let mut expected = Bitmap::new(3, 4);
+        expected.set(2, 0, true);
+        expected.set(1, 3, true);
+
+        assert_eq!(map, expected);
+
+        match "\n  .#\n  ?.\n".parse::<Bitmap>() {
+            Err(Error::InvalidPixel {
+                character: '?',
+                line_number: 3,
+            }) => (),
+
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn glyph_markers() {
+        let mut glyph = Glyph::new("period", '.');
+        glyph.set_bounds(BoundingBox {
+            width: 2,
+            height: 2,
+            x: 1,
+            y: -1,
+        });
+        glyph.set_map("#.\n.#".parse().unwrap());
+
+        assert_eq!(glyph.to_string(), "#.\n.#");
+        assert_eq!(
+            glyph.ascii().baseline("_").origin("+").to_string(),
+            "+#_\n..#"
+        );
+        assert_eq!(glyph.ascii().baseline("_").to_string(), "_#_\n..#");
+    }
+}
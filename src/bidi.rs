@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+use unicode_bidi::{BidiInfo, Level};
+
+/// Resolves the embedding levels of a paragraph, returning the paragraph
+/// level and the level of every character.
+pub(crate) fn levels(paragraph: &str) -> (u8, Vec<u8>) {
+    let info = BidiInfo::new(paragraph, None);
+    let base = info
+        .paragraphs
+        .first()
+        .map_or(0, |paragraph| paragraph.level.number());
+
+    (
+        base,
+        paragraph
+            .char_indices()
+            .map(|(offset, _)| info.levels[offset].number())
+            .collect(),
+    )
+}
+
+/// Reorders the characters of a line from logical to visual order.
+///
+/// Trailing spaces are reset to the paragraph level before the runs at odd
+/// levels are reversed.
+pub(crate) fn reorder<T>(line: &mut Vec<T>, base: u8, get: impl Fn(&T) -> (char, u8)) {
+    let trailing = line.iter().rev().take_while(|c| get(c).0 == ' ').count();
+    let levels = line
+        .iter()
+        .enumerate()
+        .map(|(offset, c)| {
+            let number = if offset >= line.len() - trailing {
+                base
+            } else {
+                get(c).1
+            };
+
+            Level::new(number).unwrap_or_else(|_| Level::ltr())
+        })
+        .collect::<Vec<_>>();
+
+    let mut logical = line.drain(..).map(Some).collect::<Vec<_>>();
+    line.extend(
+        BidiInfo::reorder_visual(&levels)
+            .into_iter()
+            .filter_map(|index| logical[index].take()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{levels, reorder};
+
+    #[test]
+    fn levels_of_mixed_text() {
+        assert_eq!(levels("ab אב"), (0, vec![0, 0, 0, 1, 1]));
+        assert_eq!(levels("אב ab"), (1, vec![1, 1, 1, 2, 2]));
+    }
+
+    #[test]
+    fn reorder_mixed_text() {
+        let text = "ab אבג cd";
+        let (base, levels) = levels(text);
+        let mut line = text.chars().zip(levels).collect::<Vec<_>>();
+        reorder(&mut line, base, |&item| item);
+
+        assert_eq!(
+            line.into_iter().map(|(c, _)| c).collect::<String>(),
+            "ab גבא cd"
+        );
+    }
+}
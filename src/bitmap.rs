@@ -1,5 +1,28 @@
+use alloc::vec::Vec;
 use bit_set::BitSet;
-use std::ops::{Deref, DerefMut};
+use core::fmt;
+use core::iter::Peekable;
+use core::ops::{Deref, DerefMut};
+
+/// How the bits of a bitmap are combined with the bits under it.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Blend {
+    /// The bits under the bitmap are replaced.
+    Replace,
+
+    /// Bits are set if they are set in either bitmap.
+    Union,
+
+    /// Bits are kept if they are set in both bitmaps, everything outside of
+    /// the bitmap is unset.
+    Intersection,
+
+    /// Bits are flipped where they are set in the bitmap.
+    Xor,
+
+    /// Bits are unset where they are set in the bitmap.
+    Difference,
+}
 
 /// The bitmap of a glyph.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -64,6 +87,411 @@ impl Bitmap {
             self.bits.remove((y * self.width + x) as usize);
         }
     }
+
+    /// Sets or unsets all bits.
+    pub fn fill(&mut self, value: bool) {
+        self.bits.clear();
+
+        if value {
+            self.bits.extend(0..(self.width * self.height) as usize);
+        }
+    }
+
+    /// Flips all bits.
+    pub fn invert(&mut self) {
+        for i in 0..(self.width * self.height) as usize {
+            if !self.bits.remove(i) {
+                self.bits.insert(i);
+            }
+        }
+    }
+
+    /// Copies the bitmap at the given position, clipping whatever falls
+    /// outside.
+    #[inline]
+    pub fn blit(&mut self, other: &Bitmap, x: i32, y: i32) {
+        self.combine(other, x, y, Blend::Replace);
+    }
+
+    /// Combines the bitmap at the given position with the given operation,
+    /// clipping whatever falls outside.
+    pub fn combine(&mut self, other: &Bitmap, x: i32, y: i32, blend: Blend) {
+        if blend == Blend::Intersection {
+            let unset = self
+                .points()
+                .filter(|&(px, py)| {
+                    let (px, py) = (px as i64 - x as i64, py as i64 - y as i64);
+
+                    px < 0
+                        || py < 0
+                        || px >= other.width as i64
+                        || py >= other.height as i64
+                        || !other.get(px as u32, py as u32)
+                })
+                .collect::<Vec<_>>();
+
+            for (px, py) in unset {
+                self.set(px, py, false);
+            }
+
+            return;
+        }
+
+        for oy in 0..other.height {
+            let py = oy as i64 + y as i64;
+
+            if py < 0 || py >= self.height as i64 {
+                continue;
+            }
+
+            for ox in 0..other.width {
+                let px = ox as i64 + x as i64;
+
+                if px < 0 || px >= self.width as i64 {
+                    continue;
+                }
+
+                let (px, py) = (px as u32, py as u32);
+                let bit = other.get(ox, oy);
+
+                match blend {
+                    Blend::Replace => self.set(px, py, bit),
+
+                    Blend::Union if bit => self.set(px, py, true),
+
+                    Blend::Xor if bit => {
+                        let current = self.get(px, py);
+                        self.set(px, py, !current);
+                    }
+
+                    Blend::Difference if bit => self.set(px, py, false),
+
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Creates an iterator over the coordinates of the set bits.
+    #[inline]
+    pub(crate) fn points(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let width = self.width;
+
+        self.bits
+            .iter()
+            .map(move |i| (i as u32 % width, i as u32 / width))
+    }
+
+    /// Creates an iterator over the horizontal runs of set bits, yielding
+    /// `(y, x_start, x_end)` row by row from the top, with `x_end` exclusive.
+    #[inline]
+    pub fn spans(&self) -> Spans<'_> {
+        Spans {
+            bits: self.bits.iter().peekable(),
+            width: self.width,
+        }
+    }
+
+    /// Gets the smallest area containing all set bits as `(x, y, width, height)`,
+    /// where `y` is the topmost row.
+    ///
+    /// Returns `None` if the bitmap is empty.
+    pub fn ink_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut points = self.points();
+        let (x, y) = points.next()?;
+        let (mut left, mut top, mut right, mut bottom) = (x, y, x, y);
+
+        for (x, y) in points {
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+        }
+
+        Some((left, top, right - left + 1, bottom - top + 1))
+    }
+
+    /// Extracts the area of the given size starting at the given position.
+    ///
+    /// The area can extend past the bitmap, in which case the bits outside of
+    /// it are unset, so this can be used for padding as well.
+    pub fn crop(&self, x: i32, y: i32, width: u32, height: u32) -> Bitmap {
+        let mut map = Bitmap::new(width, height);
+
+        for (px, py) in self.points() {
+            let px = px as i64 - x as i64;
+            let py = py as i64 - y as i64;
+
+            if px >= 0 && py >= 0 && px < width as i64 && py < height as i64 {
+                map.set(px as u32, py as u32, true);
+            }
+        }
+
+        map
+    }
+
+    /// Thickens the strokes by smearing every set bit to the right by the given
+    /// number of bits, growing the width accordingly.
+    pub fn embolden(&self, offset: u32) -> Bitmap {
+        let mut map = Bitmap::new(self.width + offset, self.height);
+
+        for (x, y) in self.points() {
+            for dx in 0..=offset {
+                map.set(x + dx, y, true);
+            }
+        }
+
+        map
+    }
+
+    /// Slants the bitmap by shifting rows one bit for every `step` rows,
+    /// growing the width accordingly.
+    ///
+    /// A positive `step` slants to the right and a negative one to the left,
+    /// a `step` of 0 keeps the bitmap as is. The `bottom` is the distance of
+    /// the bottom row from the fixed axis, so rows are shifted as if the shear
+    /// was applied around it.
+    pub fn shear(&self, step: i32, bottom: i32) -> Bitmap {
+        if self.height == 0 {
+            return self.clone();
+        }
+
+        let top = bottom + (self.height - 1) as i32;
+        let left = shift(bottom, step).min(shift(top, step));
+
+        let mut map = Bitmap::new(
+            self.width + (shift(top, step) - shift(bottom, step)).unsigned_abs(),
+            self.height,
+        );
+
+        for (x, y) in self.points() {
+            let distance = bottom + (self.height - y - 1) as i32;
+
+            map.set(x + (shift(distance, step) - left) as u32, y, true);
+        }
+
+        map
+    }
+
+    /// Enlarges the bitmap by the given factor, turning every bit into a square
+    /// of bits.
+    pub fn scale(&self, factor: u32) -> Bitmap {
+        let mut map = Bitmap::new(self.width * factor, self.height * factor);
+
+        for (x, y) in self.points() {
+            for dy in 0..factor {
+                for dx in 0..factor {
+                    map.set(x * factor + dx, y * factor + dy, true);
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Grows the set areas by the given radius, growing the bitmap by the
+    /// radius on every side.
+    pub fn dilate(&self, radius: u32) -> Bitmap {
+        let r = radius as i32;
+        let mut map = Bitmap::new(self.width + radius * 2, self.height + radius * 2);
+
+        for (x, y) in self.points() {
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    if dx * dx + dy * dy <= r * r {
+                        map.set((x as i32 + r + dx) as u32, (y as i32 + r + dy) as u32, true);
+                    }
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Shrinks the set areas by the given radius, keeping the size.
+    pub fn erode(&self, radius: u32) -> Bitmap {
+        let r = radius as i32;
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (x, y) in self.points() {
+            let inside = (-r..=r).all(|dy| {
+                (-r..=r).all(|dx| {
+                    let (x, y) = (x as i32 + dx, y as i32 + dy);
+
+                    dx * dx + dy * dy > r * r
+                        || (x >= 0
+                            && y >= 0
+                            && x < self.width as i32
+                            && y < self.height as i32
+                            && self.get(x as u32, y as u32))
+                })
+            });
+
+            if inside {
+                map.set(x, y, true);
+            }
+        }
+
+        map
+    }
+
+    /// Creates an outline of the given radius around the set areas, growing
+    /// the bitmap by the radius on every side.
+    pub fn outline(&self, radius: u32) -> Bitmap {
+        let mut map = self.dilate(radius);
+        map.combine(self, radius as i32, radius as i32, Blend::Difference);
+
+        map
+    }
+
+    /// Adds a copy moved by the given offset behind the set areas, with
+    /// positive `y` going down, growing the bitmap to fit both.
+    pub fn shadow(&self, dx: i32, dy: i32) -> Bitmap {
+        let left = (-dx).max(0);
+        let top = (-dy).max(0);
+        let mut map = Bitmap::new(
+            self.width + dx.unsigned_abs(),
+            self.height + dy.unsigned_abs(),
+        );
+
+        map.combine(self, left, top, Blend::Union);
+        map.combine(self, left + dx, top + dy, Blend::Union);
+
+        map
+    }
+
+    /// Mirrors the bitmap along the vertical axis.
+    pub fn flip_horizontal(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (x, y) in self.points() {
+            map.set(self.width - x - 1, y, true);
+        }
+
+        map
+    }
+
+    /// Mirrors the bitmap along the horizontal axis.
+    pub fn flip_vertical(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (x, y) in self.points() {
+            map.set(x, self.height - y - 1, true);
+        }
+
+        map
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.height, self.width);
+
+        for (x, y) in self.points() {
+            map.set(y, x, true);
+        }
+
+        map
+    }
+
+    /// Rotates the bitmap clockwise by 90 degrees.
+    pub fn rotate_90(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.height, self.width);
+
+        for (x, y) in self.points() {
+            map.set(self.height - y - 1, x, true);
+        }
+
+        map
+    }
+
+    /// Rotates the bitmap by 180 degrees.
+    pub fn rotate_180(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (x, y) in self.points() {
+            map.set(self.width - x - 1, self.height - y - 1, true);
+        }
+
+        map
+    }
+
+    /// Rotates the bitmap clockwise by 270 degrees.
+    pub fn rotate_270(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.height, self.width);
+
+        for (x, y) in self.points() {
+            map.set(y, self.width - x - 1, true);
+        }
+
+        map
+    }
+
+    /// Moves the contents by the given amount, with positive `y` going down
+    /// the rows, unlike `Glyph::translate` which moves the bounds up.
+    ///
+    /// The size is kept, bits moved outside of the bitmap are dropped.
+    pub fn translate(&self, dx: i32, dy: i32) -> Bitmap {
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (x, y) in self.points() {
+            let x = x as i64 + dx as i64;
+            let y = y as i64 + dy as i64;
+
+            if x >= 0 && y >= 0 && x < self.width as i64 && y < self.height as i64 {
+                map.set(x as u32, y as u32, true);
+            }
+        }
+
+        map
+    }
+}
+
+/// Gets the shift of a row at the given distance from the axis of a shear
+/// with the given step.
+pub(crate) fn shift(distance: i32, step: i32) -> i32 {
+    if step == 0 {
+        return 0;
+    }
+
+    let shift = (distance as i64).div_euclid(step.unsigned_abs() as i64) as i32;
+
+    if step > 0 {
+        shift
+    } else {
+        -shift
+    }
+}
+
+/// An iterator over the horizontal runs of set bits of a bitmap.
+#[derive(Clone)]
+pub struct Spans<'a> {
+    bits: Peekable<bit_set::Iter<'a, u32>>,
+    width: u32,
+}
+
+impl fmt::Debug for Spans<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Spans")
+            .field("width", &self.width)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for Spans<'_> {
+    type Item = (u32, u32, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.bits.next()? as u32;
+        let y = start / self.width;
+        let row = y * self.width;
+        let mut end = start + 1;
+
+        while end < row + self.width && self.bits.next_if_eq(&(end as usize)).is_some() {
+            end += 1;
+        }
+
+        Some((y, start - row, end - row))
+    }
 }
 
 impl Deref for Bitmap {
@@ -81,3 +509,214 @@ impl DerefMut for Bitmap {
         &mut self.bits
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, Blend};
+
+    fn map(width: u32, height: u32, bits: &[(u32, u32)]) -> Bitmap {
+        let mut map = Bitmap::new(width, height);
+
+        for &(x, y) in bits {
+            map.set(x, y, true);
+        }
+
+        map
+    }
+
+    #[test]
+    fn spans() {
+        let map: Bitmap = "
+            .##.#
+            #####
+            .....
+            ....#
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            map.spans().collect::<Vec<_>>(),
+            [(0, 1, 3), (0, 4, 5), (1, 0, 5), (3, 4, 5)]
+        );
+        assert_eq!(Bitmap::new(0, 0).spans().next(), None);
+    }
+
+    #[test]
+    fn ink_bounds() {
+        assert_eq!(Bitmap::new(4, 4).ink_bounds(), None);
+        assert_eq!(
+            map(5, 5, &[(1, 3), (3, 1), (2, 2)]).ink_bounds(),
+            Some((1, 1, 3, 3))
+        );
+    }
+
+    #[test]
+    fn crop() {
+        let input = map(3, 3, &[(0, 0), (1, 1), (2, 2)]);
+
+        assert_eq!(input.crop(1, 1, 2, 2), map(2, 2, &[(0, 0), (1, 1)]));
+        assert_eq!(input.crop(-1, 0, 2, 1), map(2, 1, &[(1, 0)]));
+    }
+
+    #[test]
+    fn embolden() {
+        let input = map(3, 2, &[(0, 0), (2, 1)]);
+
+        assert_eq!(input.embolden(0), input);
+        assert_eq!(
+            input.embolden(2),
+            map(5, 2, &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)])
+        );
+    }
+
+    #[test]
+    fn shear() {
+        let input = map(1, 4, &[(0, 0), (0, 1), (0, 2), (0, 3)]);
+
+        assert_eq!(
+            input.shear(2, 0),
+            map(2, 4, &[(1, 0), (1, 1), (0, 2), (0, 3)])
+        );
+        assert_eq!(
+            input.shear(2, -1),
+            map(3, 4, &[(2, 0), (1, 1), (1, 2), (0, 3)])
+        );
+        assert_eq!(
+            input.shear(-2, 0),
+            map(2, 4, &[(0, 0), (0, 1), (1, 2), (1, 3)])
+        );
+        assert_eq!(input.shear(0, 0), input);
+        assert_eq!(input.shear(i32::MIN, 0), input);
+    }
+
+    #[test]
+    fn scale() {
+        let input = map(2, 1, &[(1, 0)]);
+
+        assert_eq!(input.scale(1), input);
+        assert_eq!(input.scale(2), map(4, 2, &[(2, 0), (3, 0), (2, 1), (3, 1)]));
+    }
+
+    #[test]
+    fn dilate() {
+        let input = map(1, 1, &[(0, 0)]);
+
+        assert_eq!(input.dilate(0), input);
+        assert_eq!(
+            input.dilate(1),
+            map(3, 3, &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)])
+        );
+    }
+
+    #[test]
+    fn erode() {
+        let input = map(3, 3, &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]);
+
+        assert_eq!(input.erode(0), input);
+        assert_eq!(input.erode(1), map(3, 3, &[(1, 1)]));
+        assert_eq!(input.erode(2), Bitmap::new(3, 3));
+    }
+
+    #[test]
+    fn outline() {
+        let input = map(1, 1, &[(0, 0)]);
+
+        assert_eq!(
+            input.outline(1),
+            map(3, 3, &[(1, 0), (0, 1), (2, 1), (1, 2)])
+        );
+    }
+
+    #[test]
+    fn shadow() {
+        let input = map(1, 1, &[(0, 0)]);
+
+        assert_eq!(input.shadow(1, 1), map(2, 2, &[(0, 0), (1, 1)]));
+        assert_eq!(input.shadow(-1, 0), map(2, 1, &[(0, 0), (1, 0)]));
+    }
+
+    #[test]
+    fn fill() {
+        let mut input = map(2, 2, &[(0, 0)]);
+
+        input.fill(true);
+        assert_eq!(input, map(2, 2, &[(0, 0), (1, 0), (0, 1), (1, 1)]));
+
+        input.fill(false);
+        assert_eq!(input, Bitmap::new(2, 2));
+    }
+
+    #[test]
+    fn invert() {
+        let mut input = map(2, 2, &[(0, 0), (1, 1)]);
+        input.invert();
+
+        assert_eq!(input, map(2, 2, &[(1, 0), (0, 1)]));
+    }
+
+    #[test]
+    fn combine() {
+        let canvas = map(3, 3, &[(0, 0), (1, 1), (2, 2)]);
+        let brush = map(2, 2, &[(0, 0), (1, 0)]);
+        let apply = |x, y, blend| {
+            let mut canvas = canvas.clone();
+            canvas.combine(&brush, x, y, blend);
+            canvas
+        };
+
+        assert_eq!(
+            apply(1, 2, Blend::Replace),
+            map(3, 3, &[(0, 0), (1, 1), (1, 2), (2, 2)])
+        );
+        assert_eq!(
+            apply(-1, 1, Blend::Replace),
+            map(3, 3, &[(0, 0), (0, 1), (1, 1), (2, 2)])
+        );
+        assert_eq!(
+            apply(0, 1, Blend::Union),
+            map(3, 3, &[(0, 0), (0, 1), (1, 1), (2, 2)])
+        );
+        assert_eq!(apply(0, 1, Blend::Intersection), map(3, 3, &[(1, 1)]));
+        assert_eq!(
+            apply(0, 1, Blend::Xor),
+            map(3, 3, &[(0, 0), (0, 1), (2, 2)])
+        );
+        assert_eq!(apply(0, 1, Blend::Difference), map(3, 3, &[(0, 0), (2, 2)]));
+        assert_eq!(apply(5, 5, Blend::Union), canvas);
+    }
+
+    #[test]
+    fn flip() {
+        let input = map(3, 2, &[(0, 0), (1, 1)]);
+
+        assert_eq!(input.flip_horizontal(), map(3, 2, &[(2, 0), (1, 1)]));
+        assert_eq!(input.flip_vertical(), map(3, 2, &[(0, 1), (1, 0)]));
+    }
+
+    #[test]
+    fn rotate() {
+        let input = map(3, 2, &[(0, 0), (2, 1)]);
+
+        assert_eq!(input.rotate_90(), map(2, 3, &[(1, 0), (0, 2)]));
+        assert_eq!(input.rotate_180(), map(3, 2, &[(2, 1), (0, 0)]));
+        assert_eq!(input.rotate_270(), map(2, 3, &[(0, 2), (1, 0)]));
+        assert_eq!(input.rotate_90().rotate_270(), input);
+        assert_eq!(input.rotate_90().rotate_90(), input.rotate_180());
+    }
+
+    #[test]
+    fn transpose() {
+        let input = map(3, 2, &[(2, 0), (0, 1)]);
+
+        assert_eq!(input.transpose(), map(2, 3, &[(0, 2), (1, 0)]));
+    }
+
+    #[test]
+    fn translate() {
+        let input = map(3, 3, &[(0, 0), (2, 2)]);
+
+        assert_eq!(input.translate(1, 0), map(3, 3, &[(1, 0)]));
+        assert_eq!(input.translate(-2, -2), map(3, 3, &[(0, 0)]));
+    }
+}
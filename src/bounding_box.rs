@@ -26,3 +26,21 @@ impl Default for BoundingBox {
         }
     }
 }
+
+impl BoundingBox {
+    /// Creates the smallest bounding box containing both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        let left = self.x.min(other.x);
+        let bottom = self.y.min(other.y);
+        let right = (self.x + self.width as i32).max(other.x + other.width as i32);
+        let top = (self.y + self.height as i32).max(other.y + other.height as i32);
+
+        BoundingBox {
+            width: (right - left) as u32,
+            height: (top - bottom) as u32,
+
+            x: left,
+            y: bottom,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write as _;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::{Error, Font, Glyph, Packing};
+
+/// Generates Rust source declaring a font as a `StaticFont`, for use from
+/// build scripts or command line tools.
+///
+/// The glyph bitmaps are packed into bytes and the glyphs are sorted by
+/// codepoint, so the generated font can be queried without the standard
+/// library.
+#[derive(Clone, Debug)]
+pub struct Generator<'a> {
+    font: &'a Font,
+    name: String,
+    path: String,
+    subset: Option<BTreeSet<char>>,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates a generator declaring the font in a static with the given name.
+    #[inline]
+    pub fn new<T: Into<String>>(font: &'a Font, name: T) -> Self {
+        Generator {
+            font,
+            name: name.into(),
+            path: "bdf".into(),
+            subset: None,
+        }
+    }
+
+    /// Sets the path of this crate in the generated source, defaults to
+    /// `bdf`.
+    #[inline]
+    pub fn path<T: Into<String>>(mut self, value: T) -> Self {
+        self.path = value.into();
+        self
+    }
+
+    /// Only includes the glyphs for the given characters.
+    ///
+    /// The default glyph is always included.
+    #[inline]
+    pub fn subset<T: IntoIterator<Item = char>>(mut self, value: T) -> Self {
+        self.subset = Some(value.into_iter().collect());
+        self
+    }
+
+    /// Generates the source.
+    ///
+    /// Fails with `Error::Unrepresentable` when the metrics of a glyph do not
+    /// fit the 16-bit fields of `StaticGlyph`, and with
+    /// `Error::UnrepresentableMetric` when the ascent or descent do not fit
+    /// the ones of `StaticFont`.
+    pub fn generate(&self) -> Result<String, Error> {
+        Ok(format!(
+            "/// Generated from the `{}` font.\npub static {}: {}::StaticFont = {};\n",
+            self.font.name(),
+            self.name,
+            self.path,
+            self.expression()?
+        ))
+    }
+
+    /// Generates the expression constructing the `StaticFont`, without the
+    /// static declaration.
+    ///
+    /// Fails like `generate`.
+    pub fn expression(&self) -> Result<String, Error> {
+        let font = self.font;
+        let path = &self.path;
+        let default = font.default_glyph().map(Glyph::codepoint);
+
+        let mut glyphs = font
+            .glyphs()
+            .values()
+            .filter(|glyph| {
+                self.subset
+                    .as_ref()
+                    .map_or(true, |subset| subset.contains(&glyph.codepoint()))
+                    || Some(glyph.codepoint()) == default
+            })
+            .collect::<Vec<_>>();
+        glyphs.sort_by_key(|glyph| glyph.codepoint());
+
+        let metric = |metric: &'static str, value: u32| {
+            u16::try_from(value).map_err(|_| Error::UnrepresentableMetric {
+                metric,
+                value: value.into(),
+            })
+        };
+
+        let mut source = String::new();
+        writeln!(source, "{}::StaticFont {{", path).unwrap();
+        writeln!(source, "    name: {:?},", font.name()).unwrap();
+        writeln!(source, "    ascent: {},", metric("ascent", font.ascent())?).unwrap();
+        writeln!(
+            source,
+            "    descent: {},",
+            metric("descent", font.descent())?
+        )
+        .unwrap();
+        writeln!(source, "    default: {:?},", default).unwrap();
+        writeln!(source, "    glyphs: &[").unwrap();
+
+        for glyph in glyphs {
+            let bbx = glyph.bounds();
+
+            // The metrics are stored in 16 bits each.
+            let unrepresentable = |_| Error::Unrepresentable {
+                codepoint: glyph.codepoint(),
+            };
+            let width = u16::try_from(bbx.width).map_err(unrepresentable)?;
+            let height = u16::try_from(bbx.height).map_err(unrepresentable)?;
+            let x = i16::try_from(bbx.x).map_err(unrepresentable)?;
+            let y = i16::try_from(bbx.y).map_err(unrepresentable)?;
+            let advance = u16::try_from(font.advance(glyph)).map_err(unrepresentable)?;
+
+            writeln!(source, "        {}::StaticGlyph {{", path).unwrap();
+            writeln!(source, "            codepoint: {:?},", glyph.codepoint()).unwrap();
+            writeln!(source, "            width: {},", width).unwrap();
+            writeln!(source, "            height: {},", height).unwrap();
+            writeln!(source, "            x: {},", x).unwrap();
+            writeln!(source, "            y: {},", y).unwrap();
+            writeln!(source, "            advance: {},", advance).unwrap();
+            writeln!(source, "            bitmap: &[{}],", pack(glyph)).unwrap();
+            writeln!(source, "        }},").unwrap();
+        }
+
+        writeln!(source, "    ],").unwrap();
+        write!(source, "}}").unwrap();
+
+        Ok(source)
+    }
+
+    /// Generates the source into the given stream.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write<W: Write>(&self, mut stream: W) -> Result<(), Error> {
+        Ok(stream.write_all(self.generate()?.as_bytes())?)
+    }
+}
+
+/// Packs the rows of the glyph bitmap into bytes, padding every row to a whole
+/// byte, as a list of hexadecimal literals.
+fn pack(glyph: &Glyph) -> String {
+    glyph
+        .map()
+        .pack(Packing::default())
+        .iter()
+        .map(|byte| format!("{:#04x}", byte))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    use crate::{Bitmap, BoundingBox, Error, Font, Glyph};
+
+    #[test]
+    fn generate() {
+        let mut font = Font::new("test", None);
+        font.set_bounds(BoundingBox {
+            width: 9,
+            height: 2,
+            x: 0,
+            y: 0,
+        });
+
+        for c in "ab".chars() {
+            let mut glyph = Glyph::new(c.to_string(), c);
+            glyph.set_bounds(BoundingBox {
+                width: 9,
+                height: 2,
+                x: 0,
+                y: -1,
+            });
+            glyph.set_map("#.......#\n.#.......".parse::<Bitmap>().unwrap());
+
+            font.glyphs_mut().insert(c, glyph);
+        }
+
+        assert_eq!(
+            Generator::new(&font, "TEST")
+                .subset("b".chars())
+                .generate()
+                .unwrap(),
+            r#"/// Generated from the `test` font.
+pub static TEST: bdf::StaticFont = bdf::StaticFont {
+    name: "test",
+    ascent: 2,
+    descent: 0,
+    default: None,
+    glyphs: &[
+        bdf::StaticGlyph {
+            codepoint: 'b',
+            width: 9,
+            height: 2,
+            x: 0,
+            y: -1,
+            advance: 9,
+            bitmap: &[0x80, 0x80, 0x40, 0x00],
+        },
+    ],
+};
+"#
+        );
+    }
+
+    #[test]
+    fn unrepresentable() {
+        let mut font = Font::new("test", None);
+        let mut glyph = Glyph::new("a", 'a');
+        glyph.set_bounds(BoundingBox {
+            width: 1,
+            height: 1,
+            x: 0,
+            y: -40000,
+        });
+        font.glyphs_mut().insert('a', glyph);
+
+        assert!(matches!(
+            Generator::new(&font, "TEST").generate(),
+            Err(Error::Unrepresentable { codepoint: 'a' })
+        ));
+
+        font.glyphs_mut().clear();
+        font.set_bounds(BoundingBox {
+            width: 1,
+            height: 70000,
+            x: 0,
+            y: 0,
+        });
+
+        assert!(matches!(
+            Generator::new(&font, "TEST").expression(),
+            Err(Error::UnrepresentableMetric {
+                metric: "ascent",
+                value: 70000
+            })
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::layout::is_drawn;
+use crate::Target;
+
+/// A glyph compiled into static data by a `Generator`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct StaticGlyph {
+    /// The codepoint.
+    pub codepoint: char,
+
+    /// The width of the bitmap.
+    pub width: u16,
+
+    /// The height of the bitmap.
+    pub height: u16,
+
+    /// The horizontal offset of the bitmap from the origin.
+    pub x: i16,
+
+    /// The vertical offset of the bottom of the bitmap from the origin, with
+    /// positive `y` going up.
+    pub y: i16,
+
+    /// The horizontal advance.
+    pub advance: u16,
+
+    /// The rows of the bitmap, from the top, each padded to a whole byte with
+    /// the leftmost bit being the most significant.
+    pub bitmap: &'static [u8],
+}
+
+impl StaticGlyph {
+    /// Gets the value of the given bit.
+    ///
+    /// Bits outside of the bitmap are unset.
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        if x >= self.width as u32 || y >= self.height as u32 {
+            return false;
+        }
+
+        let stride = (self.width as usize + 7) / 8;
+        let byte = self.bitmap[y as usize * stride + x as usize / 8];
+
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Draws the glyph with the origin at the given position.
+    pub fn draw<T: Target>(&self, x: i32, y: i32, target: &mut T) {
+        let left = x + self.x as i32;
+        let top = y - self.y as i32 - self.height as i32;
+
+        for py in 0..self.height as u32 {
+            for px in 0..self.width as u32 {
+                if self.get(px, py) {
+                    target.draw(left + px as i32, top + py as i32);
+                }
+            }
+        }
+    }
+}
+
+/// A font compiled into static data by a `Generator`.
+///
+/// It needs neither parsing nor allocations, to use fonts on targets without
+/// the standard library.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct StaticFont {
+    /// The name of the font.
+    pub name: &'static str,
+
+    /// The distance from the baseline to the top of a line.
+    pub ascent: u16,
+
+    /// The distance from the baseline to the bottom of a line.
+    pub descent: u16,
+
+    /// The codepoint of the glyph used for missing characters.
+    pub default: Option<char>,
+
+    /// The glyphs, sorted by codepoint.
+    pub glyphs: &'static [StaticGlyph],
+}
+
+impl StaticFont {
+    /// Gets the distance between the baselines of two lines.
+    #[inline]
+    pub fn line_height(&self) -> u32 {
+        self.ascent as u32 + self.descent as u32
+    }
+
+    /// Gets the glyph for the character.
+    #[inline]
+    pub fn glyph(&self, codepoint: char) -> Option<&StaticGlyph> {
+        self.glyphs
+            .binary_search_by_key(&codepoint, |glyph| glyph.codepoint)
+            .ok()
+            .map(|index| &self.glyphs[index])
+    }
+
+    /// Gets the glyph for the character, or the default glyph.
+    #[inline]
+    pub fn glyph_or_default(&self, codepoint: char) -> Option<&StaticGlyph> {
+        self.glyph(codepoint)
+            .or_else(|| self.default.and_then(|default| self.glyph(default)))
+    }
+
+    /// Gets the advance width of the text, the widest line for multi-line text.
+    pub fn text_width(&self, text: &str) -> u32 {
+        text.split('\n')
+            .map(|line| {
+                line.chars()
+                    .filter(|&c| is_drawn(c))
+                    .filter_map(|c| self.glyph_or_default(c))
+                    .map(|glyph| glyph.advance as u32)
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Draws the text on the target, with the top left corner of the text box
+    /// at the given position.
+    ///
+    /// Control characters and characters without a glyph or a default glyph
+    /// are skipped, like in `Layout`.
+    pub fn draw<T: Target>(&self, text: &str, x: i32, y: i32, target: &mut T) {
+        let mut baseline = y + self.ascent as i32;
+
+        for line in text.split('\n') {
+            let mut pen = x;
+
+            for glyph in line
+                .chars()
+                .filter(|&c| is_drawn(c))
+                .filter_map(|c| self.glyph_or_default(c))
+            {
+                glyph.draw(pen, baseline, target);
+                pen += glyph.advance as i32;
+            }
+
+            baseline += self.line_height() as i32;
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use crate::{Bitmap, BoundingBox, Direction, Property};
 
 /// The possible entries in BDF.
@@ -49,19 +51,19 @@ pub enum Entry {
     Direction(Direction),
 
     /// `SWIDTH` contains the scalable width (x, y) of the glyph.
-    ScalableWidth(u32, u32),
+    ScalableWidth(i32, i32),
 
     /// `DWIDTH` contains the device width (x, y) of the glyph.
-    DeviceWidth(u32, u32),
+    DeviceWidth(i32, i32),
 
     /// `SWIDTH1` contains the alternate scalable width (x, y) of the glyph.
-    AlternateScalableWidth(u32, u32),
+    AlternateScalableWidth(i32, i32),
 
     /// `DWIDTH1` contains the alternate device width (x, y) of the glyph.
-    AlternateDeviceWidth(u32, u32),
+    AlternateDeviceWidth(i32, i32),
 
     /// `VVECTOR` contains the vector offset for the glyph.
-    Vector(u32, u32),
+    Vector(i32, i32),
 
     /// `BBX` contains the bounds for the glyph.
     BoundingBox(BoundingBox),
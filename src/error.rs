@@ -1,11 +1,15 @@
+use alloc::string::String;
+use core::num;
+#[cfg(feature = "std")]
 use std::io;
-use std::num;
 use thiserror::Error;
 
-/// Errors for `Reader` and `Writer`.
+/// Errors for `Reader`, `Writer` and ASCII art parsing.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// A downstream IO error.
+    #[cfg(feature = "std")]
     #[error("{0}")]
     IO(#[from] io::Error),
 
@@ -71,4 +75,65 @@ pub enum Error {
     /// The character declaration is malformed.
     #[error("Malformed character definition")]
     MalformedChar,
+
+    /// A line is not valid UTF-8.
+    #[error("Invalid UTF-8 on line {line_number}")]
+    Utf8 {
+        /// The line number in the font file this was encountered on
+        line_number: u32,
+    },
+
+    /// A character in ASCII art does not represent a bit.
+    #[error("Invalid pixel `{character}` on line {line_number}")]
+    InvalidPixel {
+        /// The character that was not recognized
+        character: char,
+        /// The line number in the ASCII art this was encountered on
+        line_number: u32,
+    },
+
+    /// There are fewer bytes than a packed bitmap needs.
+    #[error("Expected {expected} bytes, found {length}")]
+    Truncated {
+        /// The number of bytes the bitmap needs
+        expected: usize,
+        /// The number of bytes found
+        length: usize,
+    },
+
+    /// The metrics of a character do not fit the export format.
+    #[error("Character {codepoint:?} cannot be represented in the export format")]
+    Unrepresentable {
+        /// The codepoint of the character
+        codepoint: char,
+    },
+
+    /// The name of an exported font is not a C identifier.
+    #[error("Invalid C identifier `{name}`")]
+    InvalidName {
+        /// The name given to the font
+        name: String,
+    },
+
+    /// A metric of the font does not fit the export format.
+    #[error("Font {metric} of {value} cannot be represented in the export format")]
+    UnrepresentableMetric {
+        /// The name of the metric
+        metric: &'static str,
+        /// The value of the metric
+        value: i64,
+    },
+
+    /// None of the glyphs of the font are in the exported ranges.
+    #[error("No glyphs to export")]
+    NoGlyphs,
+
+    /// A coverage map is too large to be allocated.
+    #[error("Coverage map of {width}x{height} pixels is too large")]
+    TooLarge {
+        /// The width of the map
+        width: u32,
+        /// The height of the map
+        height: u32,
+    },
 }
@@ -0,0 +1,809 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write as _;
+use core::ops::RangeInclusive;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::{Error, Font, Glyph, Packing, Scan};
+
+/// The layouts C fonts can be exported in.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Format {
+    /// The `GFXfont` structures of the Adafruit GFX library, limited to the
+    /// Basic Multilingual Plane.
+    Gfx,
+
+    /// The run-length encoded font data of the u8g2 library, limited to
+    /// codepoints up to 255 and to 255 glyphs.
+    U8g2,
+}
+
+/// Exports a font as C source for firmware using the Adafruit GFX or u8g2
+/// libraries.
+#[derive(Clone, Debug)]
+pub struct Exporter<'a> {
+    font: &'a Font,
+    name: String,
+    format: Format,
+    ranges: Vec<RangeInclusive<char>>,
+}
+
+impl<'a> Exporter<'a> {
+    /// Creates an exporter declaring the font with the given name in the
+    /// given format.
+    #[inline]
+    pub fn new<T: Into<String>>(font: &'a Font, name: T, format: Format) -> Self {
+        Exporter {
+            font,
+            name: name.into(),
+            format,
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Only includes the glyphs in the given range of codepoints, can be
+    /// called multiple times to include multiple ranges.
+    #[inline]
+    pub fn range(mut self, value: RangeInclusive<char>) -> Self {
+        self.ranges.push(value);
+        self
+    }
+
+    /// Generates the source.
+    ///
+    /// Fails with `Error::InvalidName` when the name is not a C identifier,
+    /// with `Error::NoGlyphs` when no glyph is in the exported ranges, and
+    /// with `Error::Unrepresentable` or `Error::UnrepresentableMetric` when a
+    /// glyph or the font do not fit the format.
+    pub fn generate(&self) -> Result<String, Error> {
+        let mut chars = self.name.chars();
+        let identifier = matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+            && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+
+        if !identifier {
+            return Err(Error::InvalidName {
+                name: self.name.clone(),
+            });
+        }
+
+        match self.format {
+            Format::Gfx => self.gfx(),
+            Format::U8g2 => self.u8g2(),
+        }
+    }
+
+    /// Generates the source into the given stream.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write<W: Write>(&self, mut stream: W) -> Result<(), Error> {
+        Ok(stream.write_all(self.generate()?.as_bytes())?)
+    }
+
+    /// Gets the exported glyphs up to the given codepoint, sorted by
+    /// codepoint.
+    fn glyphs(&self, max: u32) -> Vec<&'a Glyph> {
+        let mut glyphs = self
+            .font
+            .glyphs()
+            .values()
+            .filter(|glyph| {
+                let c = glyph.codepoint();
+
+                c as u32 <= max
+                    && (self.ranges.is_empty() || self.ranges.iter().any(|r| r.contains(&c)))
+            })
+            .collect::<Vec<_>>();
+        glyphs.sort_by_key(|glyph| glyph.codepoint());
+
+        glyphs
+    }
+
+    /// Generates the Adafruit GFX source.
+    fn gfx(&self) -> Result<String, Error> {
+        let glyphs = self.glyphs(0xFFFF);
+        let mut bitmaps = Vec::new();
+        let mut entries = String::new();
+
+        if glyphs.is_empty() {
+            return Err(Error::NoGlyphs);
+        }
+
+        let line_height = self.font.line_height();
+        let line_height = u8::try_from(line_height).map_err(|_| Error::UnrepresentableMetric {
+            metric: "line height",
+            value: line_height.into(),
+        })?;
+
+        let first = glyphs[0].codepoint() as u32;
+        let last = glyphs[glyphs.len() - 1].codepoint() as u32;
+        let mut glyphs = glyphs.into_iter().peekable();
+
+        // The glyphs are indexed by their distance from the first codepoint,
+        // so codepoints without a glyph get empty entries.
+        for codepoint in first..=last {
+            let offset = bitmaps.len();
+
+            let glyph = match glyphs.next_if(|glyph| glyph.codepoint() as u32 == codepoint) {
+                Some(glyph) => glyph,
+
+                None => {
+                    writeln!(entries, "  {{ {:5}, 0, 0, 0, 0, 0 }},", offset).unwrap();
+                    continue;
+                }
+            };
+
+            let bbx = glyph.bounds();
+            let unrepresentable = |_| Error::Unrepresentable {
+                codepoint: glyph.codepoint(),
+            };
+
+            // The vertical offset is from the row above the baseline to the
+            // top of the glyph.
+            let width = u8::try_from(bbx.width).map_err(unrepresentable)?;
+            let height = u8::try_from(bbx.height).map_err(unrepresentable)?;
+            let advance = u8::try_from(self.font.advance(glyph)).map_err(unrepresentable)?;
+            let x = i8::try_from(bbx.x).map_err(unrepresentable)?;
+            let y = i8::try_from(1 - bbx.y - bbx.height as i32).map_err(unrepresentable)?;
+            let offset = u16::try_from(offset).map_err(unrepresentable)?;
+
+            // The bits of every glyph are packed without padding the rows.
+            bitmaps.extend(glyph.map().pack(Packing::new(Scan::Rows).padded(false)));
+
+            writeln!(
+                entries,
+                "  {{ {:5}, {:3}, {:3}, {:3}, {:4}, {:4} }}, // {}",
+                offset,
+                width,
+                height,
+                advance,
+                x,
+                y,
+                comment(glyph.codepoint())
+            )
+            .unwrap();
+        }
+
+        // C does not allow empty arrays, so fonts without set bits get a
+        // padding byte.
+        if bitmaps.is_empty() {
+            bitmaps.push(0);
+        }
+
+        let mut source = String::new();
+        writeln!(source, "// Generated from the `{}` font.", self.font.name()).unwrap();
+        writeln!(source, "#include <Adafruit_GFX.h>\n").unwrap();
+
+        writeln!(source, "const uint8_t {}Bitmaps[] PROGMEM = {{", self.name).unwrap();
+        write!(source, "{}", bytes(&bitmaps)).unwrap();
+        writeln!(source, "}};\n").unwrap();
+
+        writeln!(source, "const GFXglyph {}Glyphs[] PROGMEM = {{", self.name).unwrap();
+        write!(source, "{}", entries).unwrap();
+        writeln!(source, "}};\n").unwrap();
+
+        writeln!(source, "const GFXfont {} PROGMEM = {{", self.name).unwrap();
+        writeln!(source, "  (uint8_t *){}Bitmaps,", self.name).unwrap();
+        writeln!(source, "  (GFXglyph *){}Glyphs,", self.name).unwrap();
+        writeln!(source, "  {:#06x}, {:#06x}, {}", first, last, line_height).unwrap();
+        writeln!(source, "}};").unwrap();
+
+        Ok(source)
+    }
+
+    /// Generates the u8g2 source.
+    fn u8g2(&self) -> Result<String, Error> {
+        let data = self.u8g2_data()?;
+
+        let mut source = String::new();
+        writeln!(source, "// Generated from the `{}` font.", self.font.name()).unwrap();
+        writeln!(source, "#include <u8g2.h>\n").unwrap();
+
+        writeln!(
+            source,
+            "const uint8_t {}[{}] U8G2_FONT_SECTION({:?}) = {{",
+            self.name,
+            data.len(),
+            self.name
+        )
+        .unwrap();
+        write!(source, "{}", bytes(&data)).unwrap();
+        writeln!(source, "}};").unwrap();
+
+        Ok(source)
+    }
+
+    /// Generates the u8g2 font data, a header followed by the glyphs with
+    /// their metrics and run-length encoded bitmaps packed into bit fields.
+    fn u8g2_data(&self) -> Result<Vec<u8>, Error> {
+        let glyphs = self.glyphs(0xFF);
+        let font = self.font;
+
+        if glyphs.is_empty() {
+            return Err(Error::NoGlyphs);
+        }
+
+        // The glyph count is stored in a byte.
+        if let Some(glyph) = glyphs.get(255) {
+            return Err(Error::Unrepresentable {
+                codepoint: glyph.codepoint(),
+            });
+        }
+
+        // The bounding box in the header covers the exported glyphs.
+        let bbx = glyphs
+            .iter()
+            .map(|glyph| *glyph.bounds())
+            .reduce(|bbx, other| bbx.union(&other))
+            .unwrap();
+
+        // The metrics are stored in at most 8 bits each.
+        let metrics = glyphs
+            .iter()
+            .map(|glyph| {
+                let bbx = glyph.bounds();
+                let advance = font.advance(glyph) as i32;
+
+                if bbx.width > 255
+                    || bbx.height > 255
+                    || !(-128..128).contains(&bbx.x)
+                    || !(-128..128).contains(&bbx.y)
+                    || advance > 127
+                {
+                    return Err(Error::Unrepresentable {
+                        codepoint: glyph.codepoint(),
+                    });
+                }
+
+                Ok((bbx.width, bbx.height, bbx.x, bbx.y, advance))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let widths = unsigned(metrics.iter().map(|m| m.0));
+        let heights = unsigned(metrics.iter().map(|m| m.1));
+        let xs = signed(metrics.iter().map(|m| m.2));
+        let ys = signed(metrics.iter().map(|m| m.3));
+        let advances = signed(metrics.iter().map(|m| m.4));
+
+        // Pick the sizes of the run lengths producing the smallest font.
+        let runs = glyphs.iter().map(|glyph| runs(glyph)).collect::<Vec<_>>();
+        let (zeros, ones) = (2..=8)
+            .flat_map(|zeros| (2..=8).map(move |ones| (zeros, ones)))
+            .min_by_key(|&(zeros, ones)| {
+                runs.iter()
+                    .map(|runs| encode(runs, zeros, ones).len as usize)
+                    .sum::<usize>()
+            })
+            .unwrap();
+
+        let mut data = Vec::new();
+        let mut upper = None;
+        let mut lower = None;
+
+        for ((glyph, runs), m) in glyphs.iter().zip(&runs).zip(&metrics) {
+            let codepoint = glyph.codepoint();
+            let mut bits = Bits::default();
+
+            bits.push(m.0, widths);
+            bits.push(m.1, heights);
+            bits.push((m.2 + (1 << (xs - 1))) as u32, xs);
+            bits.push((m.3 + (1 << (ys - 1))) as u32, ys);
+            bits.push((m.4 + (1 << (advances - 1))) as u32, advances);
+
+            if m.0 > 0 {
+                bits.extend(&encode(runs, zeros, ones));
+            }
+
+            let size = u8::try_from(bits.bytes.len() + 2)
+                .map_err(|_| Error::Unrepresentable { codepoint })?;
+
+            if codepoint >= 'A' && upper.is_none() {
+                upper = Some(data.len());
+            }
+
+            if codepoint >= 'a' && lower.is_none() {
+                lower = Some(data.len());
+            }
+
+            data.push(codepoint as u8);
+            data.push(size);
+            data.extend(bits.bytes);
+        }
+
+        let end = data.len();
+
+        // The glyphs end with an empty glyph, followed by an empty table for
+        // codepoints above 255.
+        data.extend([0, 0]);
+        let unicode = data.len();
+        data.extend([0, 4, 0xFF, 0xFF, 0, 0]);
+
+        let top = |c: char| {
+            font.glyphs()
+                .get(&c)
+                .map(|glyph| glyph.bounds().y + glyph.bounds().height as i32)
+        };
+        let bottom = |c: char| font.glyphs().get(&c).map(|glyph| glyph.bounds().y);
+
+        let ascent = font.ascent() as i32;
+        let descent = -(font.descent() as i32);
+
+        let mut header = vec![
+            glyphs.len() as u8,
+            0,
+            zeros as u8,
+            ones as u8,
+            widths as u8,
+            heights as u8,
+            xs as u8,
+            ys as u8,
+            advances as u8,
+            bbx.width.min(255) as u8,
+            bbx.height.min(255) as u8,
+            bbx.x.clamp(-128, 127) as i8 as u8,
+            bbx.y.clamp(-128, 127) as i8 as u8,
+            top('A').unwrap_or(ascent).clamp(-128, 127) as i8 as u8,
+            bottom('g').unwrap_or(descent).clamp(-128, 127) as i8 as u8,
+            top('(').unwrap_or(ascent).clamp(-128, 127) as i8 as u8,
+            bottom(')').unwrap_or(descent).clamp(-128, 127) as i8 as u8,
+        ];
+
+        for offset in [upper.unwrap_or(end), lower.unwrap_or(end), unicode] {
+            let offset = u16::try_from(offset).map_err(|_| Error::Unrepresentable {
+                codepoint: glyphs.last().unwrap().codepoint(),
+            })?;
+
+            header.extend(offset.to_be_bytes());
+        }
+
+        header.extend(data);
+
+        Ok(header)
+    }
+}
+
+/// Formats the bytes as rows of hexadecimal literals.
+fn bytes(bytes: &[u8]) -> String {
+    bytes
+        .chunks(12)
+        .map(|row| {
+            let row = row
+                .iter()
+                .map(|byte| format!("{:#04x}", byte))
+                .collect::<Vec<_>>();
+
+            format!("  {},\n", row.join(", "))
+        })
+        .collect()
+}
+
+/// Describes the codepoint in a C comment.
+fn comment(codepoint: char) -> String {
+    if codepoint.is_ascii_graphic() || codepoint == ' ' {
+        format!("{:#06x} '{}'", codepoint as u32, codepoint)
+    } else {
+        format!("{:#06x}", codepoint as u32)
+    }
+}
+
+/// Gets the number of bits needed for the unsigned values.
+fn unsigned<I: Iterator<Item = u32>>(values: I) -> u32 {
+    (32 - values.max().unwrap_or(0).leading_zeros()).max(1)
+}
+
+/// Gets the number of bits needed for the signed values, stored with an
+/// offset of half their range.
+fn signed<I: Iterator<Item = i32>>(values: I) -> u32 {
+    values
+        .map(|value| 33 - if value < 0 { !value } else { value }.leading_zeros())
+        .max()
+        .unwrap_or(1)
+}
+
+/// Gets the lengths of the alternating runs of unset and set bits of the
+/// glyph, read row by row and starting with unset bits.
+fn runs(glyph: &Glyph) -> Vec<u32> {
+    let mut runs = vec![0];
+
+    for y in 0..glyph.height() {
+        for x in 0..glyph.width() {
+            if glyph.get(x, y) != (runs.len() % 2 == 0) {
+                runs.push(0);
+            }
+
+            *runs.last_mut().unwrap() += 1;
+        }
+    }
+
+    runs
+}
+
+/// Encodes the runs as pairs of unset and set bit counts of the given sizes,
+/// each followed by a bit repeating it.
+fn encode(runs: &[u32], zeros: u32, ones: u32) -> Bits {
+    let (max0, max1) = ((1 << zeros) - 1, (1 << ones) - 1);
+    let mut pairs = Vec::new();
+
+    // Runs too long for a pair are split over pairs with empty runs.
+    for run in runs.chunks(2) {
+        let (mut a, mut b) = (run[0], run.get(1).copied().unwrap_or(0));
+
+        while a > max0 {
+            pairs.push((max0, 0));
+            a -= max0;
+        }
+
+        pairs.push((a, b.min(max1)));
+        b -= b.min(max1);
+
+        while b > 0 {
+            pairs.push((0, b.min(max1)));
+            b -= b.min(max1);
+        }
+    }
+
+    let mut bits = Bits::default();
+    let mut pairs = pairs.into_iter().peekable();
+
+    while let Some(pair) = pairs.next() {
+        bits.push(pair.0, zeros);
+        bits.push(pair.1, ones);
+
+        while pairs.next_if_eq(&pair).is_some() {
+            bits.push(1, 1);
+        }
+
+        bits.push(0, 1);
+    }
+
+    bits
+}
+
+/// A stream of bits, filling bytes from the least significant bit.
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    len: u32,
+}
+
+impl Bits {
+    /// Appends the lowest `count` bits of the value.
+    fn push(&mut self, value: u32, count: u32) {
+        for bit in 0..count {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+
+            if value >> bit & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.len % 8);
+            }
+
+            self.len += 1;
+        }
+    }
+
+    /// Appends the bits of the other stream.
+    fn extend(&mut self, other: &Bits) {
+        for bit in 0..other.len {
+            self.push((other.bytes[bit as usize / 8] >> (bit % 8)) as u32, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Exporter, Format};
+    use crate::{Bitmap, BoundingBox, Error, Font, Glyph};
+
+    fn font() -> Font {
+        let mut font = Font::new("test", None);
+        font.set_bounds(BoundingBox {
+            width: 9,
+            height: 3,
+            x: 0,
+            y: -1,
+        });
+
+        let maps = [
+            ('A', "#.......#\n.#######.\n#.......#"),
+            ('a', "#########\n.........\n##.....##"),
+            ('b', ".........\n.........\n........."),
+        ];
+
+        for (c, map) in maps.iter() {
+            let mut glyph = Glyph::new(c.to_string(), *c);
+            glyph.set_bounds(BoundingBox {
+                width: 9,
+                height: 3,
+                x: 1,
+                y: -1,
+            });
+            glyph.set_map(map.parse::<Bitmap>().unwrap());
+
+            font.glyphs_mut().insert(*c, glyph);
+        }
+
+        font
+    }
+
+    /// Reads bit fields the way u8g2 does.
+    struct Reader<'a> {
+        data: &'a [u8],
+        bit: usize,
+    }
+
+    impl Reader<'_> {
+        fn unsigned(&mut self, count: u8) -> u32 {
+            let mut value = 0;
+
+            for i in 0..count {
+                value |= ((self.data[self.bit / 8] >> (self.bit % 8)) as u32 & 1) << i;
+                self.bit += 1;
+            }
+
+            value
+        }
+
+        fn signed(&mut self, count: u8) -> i32 {
+            self.unsigned(count) as i32 - (1 << (count - 1))
+        }
+    }
+
+    #[test]
+    fn u8g2() {
+        let font = font();
+        let data = Exporter::new(&font, "test", Format::U8g2)
+            .u8g2_data()
+            .unwrap();
+
+        assert_eq!(data[0], 3);
+        assert_eq!(&data[9..13], &[9, 3, 1, 0xFF]);
+        assert_eq!(data[13] as i8, 2);
+
+        // The glyphs for letters are found from their start positions.
+        let glyphs = &data[23..];
+        let upper = u16::from_be_bytes([data[17], data[18]]) as usize;
+        let lower = u16::from_be_bytes([data[19], data[20]]) as usize;
+        let unicode = u16::from_be_bytes([data[21], data[22]]) as usize;
+
+        assert_eq!(glyphs[upper], b'A');
+        assert_eq!(glyphs[lower], b'a');
+        assert_eq!(&glyphs[unicode..], &[0, 4, 0xFF, 0xFF, 0, 0]);
+
+        let mut offset = 0;
+
+        while glyphs[offset + 1] != 0 {
+            let c = glyphs[offset] as char;
+            let glyph = &font.glyphs()[&c];
+
+            let mut reader = Reader {
+                data: &glyphs[offset + 2..],
+                bit: 0,
+            };
+
+            let width = reader.unsigned(data[4]);
+            let height = reader.unsigned(data[5]);
+            let bbx = BoundingBox {
+                width,
+                height,
+                x: reader.signed(data[6]),
+                y: reader.signed(data[7]),
+            };
+
+            assert_eq!(&bbx, glyph.bounds());
+            assert_eq!(reader.signed(data[8]), 9);
+
+            let mut map = Bitmap::new(width, height);
+            let mut pixel = 0;
+
+            while pixel < width * height {
+                let zeros = reader.unsigned(data[2]);
+                let ones = reader.unsigned(data[3]);
+
+                loop {
+                    for i in pixel + zeros..pixel + zeros + ones {
+                        map.set(i % width, i / width, true);
+                    }
+
+                    pixel += zeros + ones;
+
+                    if reader.unsigned(1) == 0 {
+                        break;
+                    }
+                }
+            }
+
+            assert_eq!(pixel, width * height);
+            assert_eq!(&map, glyph.map());
+
+            offset += glyphs[offset + 1] as usize;
+        }
+
+        assert_eq!(offset, unicode - 2);
+    }
+
+    #[test]
+    fn u8g2_unrepresentable() {
+        let mut font = font();
+        font.glyphs_mut()
+            .get_mut(&'b')
+            .unwrap()
+            .set_device_width(Some((200, 0)));
+
+        assert!(matches!(
+            Exporter::new(&font, "test", Format::U8g2).generate(),
+            Err(Error::Unrepresentable { codepoint: 'b' })
+        ));
+    }
+
+    #[test]
+    fn u8g2_without_bounds() {
+        let mut font = Font::new("test", None);
+
+        for c in (0..=255).filter_map(core::char::from_u32) {
+            let mut glyph = Glyph::new(c.to_string(), c);
+            glyph.set_bounds(BoundingBox {
+                width: 1,
+                height: 2,
+                x: c as i32 % 3,
+                y: -1,
+            });
+            glyph.set_map(Bitmap::new(1, 2));
+
+            font.glyphs_mut().insert(c, glyph);
+        }
+
+        let data = Exporter::new(&font, "test", Format::U8g2)
+            .range('\u{1}'..='\u{FF}')
+            .u8g2_data()
+            .unwrap();
+
+        assert_eq!(data[0], 255);
+        assert_eq!(&data[9..13], &[3, 2, 0, 0xFF]);
+
+        assert!(matches!(
+            Exporter::new(&font, "test", Format::U8g2).generate(),
+            Err(Error::Unrepresentable {
+                codepoint: '\u{FF}'
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_name() {
+        let font = font();
+
+        for name in &["", "1font", "my-font", "font name", "fönt"] {
+            assert!(matches!(
+                Exporter::new(&font, *name, Format::Gfx).generate(),
+                Err(Error::InvalidName { .. })
+            ));
+        }
+
+        assert!(Exporter::new(&font, "_font1", Format::U8g2)
+            .generate()
+            .is_ok());
+    }
+
+    #[test]
+    fn gfx() {
+        let font = font();
+
+        assert_eq!(
+            Exporter::new(&font, "Test", Format::Gfx)
+                .range('a'..='z')
+                .generate()
+                .unwrap(),
+            r#"// Generated from the `test` font.
+#include <Adafruit_GFX.h>
+
+const uint8_t TestBitmaps[] PROGMEM = {
+  0xff, 0x80, 0x30, 0x60, 0x00, 0x00, 0x00, 0x00,
+};
+
+const GFXglyph TestGlyphs[] PROGMEM = {
+  {     0,   9,   3,   9,    1,   -1 }, // 0x0061 'a'
+  {     4,   9,   3,   9,    1,   -1 }, // 0x0062 'b'
+};
+
+const GFXfont Test PROGMEM = {
+  (uint8_t *)TestBitmaps,
+  (GFXglyph *)TestGlyphs,
+  0x0061, 0x0062, 3
+};
+"#
+        );
+    }
+
+    #[test]
+    fn gfx_gaps() {
+        let font = font();
+        let source = Exporter::new(&font, "Test", Format::Gfx)
+            .range('A'..='A')
+            .range('a'..='a')
+            .generate()
+            .unwrap();
+
+        assert_eq!(source.matches("  {     4, 0, 0, 0, 0, 0 },").count(), 31);
+        assert!(source.contains("  0x0041, 0x0061, 3\n"));
+    }
+
+    #[test]
+    fn u8g2_parentheses() {
+        let mut font = font();
+
+        for (c, y) in [('(', -2), (')', -3)] {
+            let mut glyph = Glyph::new(c.to_string(), c);
+            glyph.set_bounds(BoundingBox {
+                width: 1,
+                height: 5,
+                x: 0,
+                y,
+            });
+            glyph.set_map(Bitmap::new(1, 5));
+
+            font.glyphs_mut().insert(c, glyph);
+        }
+
+        let data = Exporter::new(&font, "test", Format::U8g2)
+            .u8g2_data()
+            .unwrap();
+
+        // The extent of parentheses goes from the top of `(` to the bottom
+        // of `)`.
+        assert_eq!(data[15] as i8, 3);
+        assert_eq!(data[16] as i8, -3);
+    }
+
+    #[test]
+    fn empty() {
+        let font = font();
+
+        for format in [Format::Gfx, Format::U8g2] {
+            assert!(matches!(
+                Exporter::new(&font, "test", format)
+                    .range('0'..='9')
+                    .generate(),
+                Err(Error::NoGlyphs)
+            ));
+        }
+    }
+
+    #[test]
+    fn gfx_blank() {
+        let mut font = font();
+        let mut glyph = Glyph::new("space", ' ');
+        glyph.set_bounds(BoundingBox::default());
+        glyph.set_device_width(Some((4, 0)));
+        font.glyphs_mut().insert(' ', glyph);
+
+        let source = Exporter::new(&font, "Test", Format::Gfx)
+            .range(' '..=' ')
+            .generate()
+            .unwrap();
+
+        assert!(source.contains("TestBitmaps[] PROGMEM = {\n  0x00,\n};"));
+    }
+
+    #[test]
+    fn gfx_unrepresentable() {
+        let mut font = font();
+        font.set_bounds(BoundingBox {
+            width: 9,
+            height: 300,
+            x: 0,
+            y: -1,
+        });
+
+        assert!(matches!(
+            Exporter::new(&font, "Test", Format::Gfx).generate(),
+            Err(Error::UnrepresentableMetric {
+                metric: "line height",
+                value: 300
+            })
+        ));
+    }
+}
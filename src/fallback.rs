@@ -0,0 +1,237 @@
+use alloc::borrow::Cow;
+use alloc::format;
+
+use crate::{Bitmap, BoundingBox, Font, Glyph, Property};
+
+/// The rows of the hexadecimal digits drawn in missing glyph boxes, 3 bits
+/// wide and 5 rows tall.
+const DIGITS: [[u8; 5]; 16] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    [0b010, 0b101, 0b111, 0b101, 0b101],
+    [0b110, 0b101, 0b110, 0b101, 0b110],
+    [0b011, 0b100, 0b100, 0b100, 0b011],
+    [0b110, 0b101, 0b101, 0b101, 0b110],
+    [0b111, 0b100, 0b111, 0b100, 0b111],
+    [0b111, 0b100, 0b111, 0b100, 0b100],
+];
+
+impl Font {
+    /// Gets the glyph drawn for characters missing from the font, the glyph for
+    /// the `DEFAULT_CHAR` property or the glyph named `.notdef`.
+    pub fn default_glyph(&self) -> Option<&Glyph> {
+        let default = match self.properties().get("DEFAULT_CHAR") {
+            Some(&Property::Integer(value)) => core::char::from_u32(value as u32)
+                .and_then(|codepoint| self.glyphs().get(&codepoint)),
+
+            _ => None,
+        };
+
+        default.or_else(|| {
+            self.glyphs()
+                .values()
+                .find(|glyph| glyph.name() == ".notdef")
+        })
+    }
+
+    /// Gets the glyph for the character, falling back to the default glyph
+    /// and then to a box the size of the default bounding box showing the
+    /// hexadecimal digits of the codepoint.
+    ///
+    /// The default glyph is looked up and the box glyph is built on every
+    /// call, `Layout` and `Renderer` look up the default glyph and the size of
+    /// the box once instead.
+    pub fn glyph_or_fallback(&self, codepoint: char) -> Cow<'_, Glyph> {
+        match self
+            .glyphs()
+            .get(&codepoint)
+            .or_else(|| self.default_glyph())
+        {
+            Some(glyph) => Cow::Borrowed(glyph),
+
+            None => Cow::Owned(self.missing_glyph(codepoint)),
+        }
+    }
+
+    /// Creates a box glyph for a missing character, showing the hexadecimal
+    /// digits of the codepoint when they fit in the default bounding box, or
+    /// in the bounds of all the glyphs for fonts without one.
+    ///
+    /// The glyph is built on every call, and fonts without a default bounding
+    /// box go through all their glyphs to size it, so callers drawing many
+    /// missing characters should keep the result.
+    #[inline]
+    pub fn missing_glyph(&self, codepoint: char) -> Glyph {
+        missing_glyph(self.max_bounds(), codepoint)
+    }
+}
+
+/// Creates a box glyph for a missing character with the given bounds.
+pub(crate) fn missing_glyph(bounds: BoundingBox, codepoint: char) -> Glyph {
+    let (width, height) = (bounds.width, bounds.height);
+    let mut map = Bitmap::new(width, height);
+
+    if width > 0 && height > 0 {
+        for x in 0..width {
+            map.set(x, 0, true);
+            map.set(x, height - 1, true);
+        }
+
+        for y in 0..height {
+            map.set(0, y, true);
+            map.set(width - 1, y, true);
+        }
+    }
+
+    // The digits go on two rows, with a space between them and around the
+    // border.
+    let digits = if codepoint as u32 > 0xFFFF {
+        format!("{:06X}", codepoint as u32)
+    } else {
+        format!("{:04X}", codepoint as u32)
+    };
+    let columns = digits.len() as u32 / 2;
+    let inner = (columns * 4 - 1, 11);
+
+    if width >= inner.0 + 4 && height >= inner.1 + 4 {
+        let left = (width - inner.0) / 2;
+        let top = (height - inner.1) / 2;
+
+        for (i, digit) in digits.chars().enumerate() {
+            let rows = DIGITS[digit.to_digit(16).unwrap() as usize];
+            let x = left + (i as u32 % columns) * 4;
+            let y = top + (i as u32 / columns) * 6;
+
+            for (dy, row) in rows.iter().enumerate() {
+                for dx in 0..3 {
+                    if row & (0b100 >> dx) != 0 {
+                        map.set(x + dx, y + dy as u32, true);
+                    }
+                }
+            }
+        }
+    }
+
+    // Glyph names follow the Adobe Glyph List, which only uses `uni` for the
+    // Basic Multilingual Plane.
+    let name = if codepoint as u32 > 0xFFFF {
+        format!("u{:X}", codepoint as u32)
+    } else {
+        format!("uni{:04X}", codepoint as u32)
+    };
+
+    let mut glyph = Glyph::new(name, codepoint);
+    glyph.set_bounds(bounds);
+    glyph.set_device_width(Some((width as i32, 0)));
+    glyph.set_map(map);
+
+    glyph
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, BoundingBox, Font, Glyph, Layout, Property};
+
+    fn font(width: u32, height: u32) -> Font {
+        let mut font = Font::new("test", None);
+        font.set_bounds(BoundingBox {
+            width,
+            height,
+            x: 0,
+            y: -1,
+        });
+
+        font
+    }
+
+    #[test]
+    fn default_glyph() {
+        let mut font = font(4, 4);
+        assert!(font.default_glyph().is_none());
+
+        let mut glyph = Glyph::new(".notdef", '\u{0}');
+        glyph.set_map(Bitmap::new(1, 1));
+        font.glyphs_mut().insert('\u{0}', glyph);
+        assert_eq!(font.glyph_or_fallback('a').name(), ".notdef");
+
+        font.glyphs_mut().insert('?', Glyph::new("question", '?'));
+        font.properties_mut()
+            .insert("DEFAULT_CHAR".into(), Property::Integer(63));
+        assert_eq!(font.glyph_or_fallback('a').name(), "question");
+    }
+
+    #[test]
+    fn missing_glyph() {
+        let small = font(4, 3);
+        let glyph = small.glyph_or_fallback('a');
+
+        assert_eq!(glyph.name(), "uni0061");
+        assert_eq!(small.missing_glyph('\u{1F600}').name(), "u1F600");
+        assert_eq!(glyph.device_width(), Some(&(4, 0)));
+        assert_eq!(glyph.bounds().y, -1);
+        assert_eq!(
+            glyph.map(),
+            &"
+                ####
+                #..#
+                ####
+            "
+            .parse()
+            .unwrap()
+        );
+
+        assert_eq!(
+            font(11, 15).missing_glyph('a').map(),
+            &"
+                ###########
+                #.........#
+                #.###.###.#
+                #.#.#.#.#.#
+                #.#.#.#.#.#
+                #.#.#.#.#.#
+                #.###.###.#
+                #.........#
+                #.###..#..#
+                #.#...##..#
+                #.###..#..#
+                #.#.#..#..#
+                #.###.###.#
+                #.........#
+                ###########
+            "
+            .parse()
+            .unwrap()
+        );
+        assert!(font(15, 15).missing_glyph('\u{1F600}').map().get(2, 2));
+    }
+
+    #[test]
+    fn missing_glyph_without_bounds() {
+        let bounds = BoundingBox {
+            width: 4,
+            height: 3,
+            x: 0,
+            y: -1,
+        };
+        let mut font = Font::new("test", None);
+        let mut glyph = Glyph::new("b", 'b');
+        glyph.set_bounds(bounds);
+        glyph.set_map(Bitmap::new(4, 3));
+        font.glyphs_mut().insert('b', glyph);
+
+        let glyph = font.missing_glyph('a');
+        assert_eq!(glyph.bounds(), &bounds);
+        assert_eq!(glyph.device_width(), Some(&(4, 0)));
+
+        let runs = Layout::new(&font).runs("aa");
+        assert_eq!(runs[0].width, 8);
+    }
+}
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
 
-use crate::{BoundingBox, Direction, Glyph, Property};
+use crate::bitmap::shift;
+use crate::{math, xlfd, BoundingBox, Direction, Glyph, Property, Scaler};
 
 /// Size of a font.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -15,6 +18,79 @@ pub struct Size {
     pub y: u16,
 }
 
+/// The slant of an oblique font.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Slant {
+    /// Angle from the vertical in degrees, negative to slant to the left.
+    Angle(f32),
+
+    /// Number of rows for every bit of horizontal shift, negative to slant to
+    /// the left.
+    Step(i32),
+}
+
+impl Slant {
+    /// Gets the number of rows for every bit of horizontal shift, negative
+    /// when slanting to the left, or `None` when upright.
+    ///
+    /// Panics if the angle is not between -90 and 90 degrees.
+    pub fn step(&self) -> Option<i32> {
+        match *self {
+            Slant::Angle(angle) => {
+                assert!(angle > -90.0 && angle < 90.0, "invalid slant angle");
+
+                if angle == 0.0 {
+                    return None;
+                }
+
+                // Steps too big to fit are as good as upright for any glyph.
+                let step = math::round(1.0 / math::tan(angle.to_radians())) as i32;
+
+                Some(if angle > 0.0 {
+                    step.max(1)
+                } else {
+                    step.min(-1)
+                })
+            }
+
+            Slant::Step(0) => None,
+
+            Slant::Step(step) => Some(step),
+        }
+    }
+}
+
+/// Properties holding pixel or decipoint metrics, scaled along with the glyphs.
+const SCALED_PROPERTIES: &[&str] = &[
+    "PIXEL_SIZE",
+    "POINT_SIZE",
+    "AVERAGE_WIDTH",
+    "FONT_ASCENT",
+    "FONT_DESCENT",
+    "MIN_SPACE",
+    "NORM_SPACE",
+    "MAX_SPACE",
+    "END_SPACE",
+    "AVG_CAPITAL_WIDTH",
+    "AVG_LOWERCASE_WIDTH",
+    "AVG_UPPERCASE_WIDTH",
+    "QUAD_WIDTH",
+    "FIGURE_WIDTH",
+    "SUPERSCRIPT_X",
+    "SUPERSCRIPT_Y",
+    "SUPERSCRIPT_SIZE",
+    "SUBSCRIPT_X",
+    "SUBSCRIPT_Y",
+    "SUBSCRIPT_SIZE",
+    "SMALL_CAP_SIZE",
+    "UNDERLINE_POSITION",
+    "UNDERLINE_THICKNESS",
+    "STRIKEOUT_ASCENT",
+    "STRIKEOUT_DESCENT",
+    "CAP_HEIGHT",
+    "X_HEIGHT",
+];
+
 /// A BDF font.
 #[derive(Clone, Debug)]
 pub struct Font {
@@ -28,16 +104,16 @@ pub struct Font {
 
     direction: Direction,
 
-    scalable_width: Option<(u32, u32)>,
-    device_width: Option<(u32, u32)>,
+    scalable_width: Option<(i32, i32)>,
+    device_width: Option<(i32, i32)>,
 
-    alternate_scalable_width: Option<(u32, u32)>,
-    alternate_device_width: Option<(u32, u32)>,
+    alternate_scalable_width: Option<(i32, i32)>,
+    alternate_device_width: Option<(i32, i32)>,
 
-    vector: Option<(u32, u32)>,
+    vector: Option<(i32, i32)>,
 
-    properties: HashMap<String, Property>,
-    glyphs: HashMap<char, Glyph>,
+    properties: BTreeMap<String, Property>,
+    glyphs: BTreeMap<char, Glyph>,
 }
 
 impl Default for Font {
@@ -62,8 +138,8 @@ impl Default for Font {
 
             vector: None,
 
-            properties: HashMap::new(),
-            glyphs: HashMap::new(),
+            properties: BTreeMap::new(),
+            glyphs: BTreeMap::new(),
         }
     }
 }
@@ -171,85 +247,362 @@ impl Font {
 
     /// Gets the default scalable width.
     #[inline]
-    pub fn scalable_width(&self) -> Option<&(u32, u32)> {
+    pub fn scalable_width(&self) -> Option<&(i32, i32)> {
         self.scalable_width.as_ref()
     }
 
     /// Sets the default scalable width.
     #[inline]
-    pub fn set_scalable_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_scalable_width(&mut self, value: Option<(i32, i32)>) {
         self.scalable_width = value;
     }
 
     /// Gets the default device width.
     #[inline]
-    pub fn device_width(&self) -> Option<&(u32, u32)> {
+    pub fn device_width(&self) -> Option<&(i32, i32)> {
         self.device_width.as_ref()
     }
 
     /// Sets the default device width.
     #[inline]
-    pub fn set_device_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_device_width(&mut self, value: Option<(i32, i32)>) {
         self.device_width = value;
     }
 
     /// Gets the default alternate scalable width.
     #[inline]
-    pub fn alternate_scalable_width(&self) -> Option<&(u32, u32)> {
+    pub fn alternate_scalable_width(&self) -> Option<&(i32, i32)> {
         self.alternate_scalable_width.as_ref()
     }
 
     /// Sets the default alternate scalable width.
     #[inline]
-    pub fn set_alternate_scalable_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_alternate_scalable_width(&mut self, value: Option<(i32, i32)>) {
         self.alternate_scalable_width = value;
     }
 
     /// Gets the default alternate device width.
     #[inline]
-    pub fn alternate_device_width(&self) -> Option<&(u32, u32)> {
+    pub fn alternate_device_width(&self) -> Option<&(i32, i32)> {
         self.alternate_device_width.as_ref()
     }
 
     /// Sets the default alternate device width.
     #[inline]
-    pub fn set_alternate_device_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_alternate_device_width(&mut self, value: Option<(i32, i32)>) {
         self.alternate_device_width = value;
     }
 
     /// Gets the default offset vector.
     #[inline]
-    pub fn vector(&self) -> Option<&(u32, u32)> {
+    pub fn vector(&self) -> Option<&(i32, i32)> {
         self.vector.as_ref()
     }
 
     /// Sets the default offset vector.
     #[inline]
-    pub fn set_vector(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_vector(&mut self, value: Option<(i32, i32)>) {
         self.vector = value;
     }
 
     /// Gets the properties.
     #[inline]
-    pub fn properties(&self) -> &HashMap<String, Property> {
+    pub fn properties(&self) -> &BTreeMap<String, Property> {
         &self.properties
     }
 
     /// Gets a mutable reference to the properties.
     #[inline]
-    pub fn properties_mut(&mut self) -> &mut HashMap<String, Property> {
+    pub fn properties_mut(&mut self) -> &mut BTreeMap<String, Property> {
         &mut self.properties
     }
 
+    /// Gets the distance from the baseline to the top of a line, from the
+    /// `FONT_ASCENT` property or the default bounding box.
+    pub fn ascent(&self) -> u32 {
+        match self.properties.get("FONT_ASCENT") {
+            Some(&Property::Integer(value)) => value.max(0) as u32,
+
+            _ => self
+                .bounds
+                .map_or(0, |bbx| (bbx.y + bbx.height as i32).max(0) as u32),
+        }
+    }
+
+    /// Gets the distance from the baseline to the bottom of a line, from the
+    /// `FONT_DESCENT` property or the default bounding box.
+    pub fn descent(&self) -> u32 {
+        match self.properties.get("FONT_DESCENT") {
+            Some(&Property::Integer(value)) => value.max(0) as u32,
+
+            _ => self.bounds.map_or(0, |bbx| (-bbx.y).max(0) as u32),
+        }
+    }
+
+    /// Gets the horizontal advance of the glyph, from its device width, the
+    /// default device width or its bounds.
+    pub fn advance(&self, glyph: &Glyph) -> u32 {
+        glyph
+            .device_width()
+            .or(self.device_width.as_ref())
+            .map_or_else(|| glyph.bounds().width, |&(x, _)| x.unsigned_abs())
+    }
+
+    /// Gets the horizontal advance of the glyph when written right-to-left,
+    /// from the length of the horizontal component of its alternate device
+    /// width when the glyph or the font have alternate metrics, or its advance.
+    pub fn alternate_advance(&self, glyph: &Glyph) -> u32 {
+        if glyph.direction() == Direction::Default && self.direction == Direction::Default {
+            return self.advance(glyph);
+        }
+
+        match glyph
+            .alternate_device_width()
+            .or(self.alternate_device_width.as_ref())
+        {
+            Some(&(x, _)) if x != 0 => x.unsigned_abs(),
+
+            _ => self.advance(glyph),
+        }
+    }
+
+    /// Gets the vertical advance of the glyph going down, from the length of
+    /// the vertical component of its alternate device width, which is usually
+    /// negative, the default alternate device width or the line height.
+    pub fn vertical_advance(&self, glyph: &Glyph) -> u32 {
+        glyph
+            .alternate_device_width()
+            .or(self.alternate_device_width.as_ref())
+            .map_or_else(
+                || self.ascent() + self.descent(),
+                |&(_, y)| y.unsigned_abs(),
+            )
+    }
+
+    /// Gets the offset from the horizontal origin of the glyph to its vertical
+    /// origin, with positive `y` going up, from its offset vector, the default
+    /// offset vector or the middle of the bounding box at the ascent.
+    pub fn vertical_origin(&self, glyph: &Glyph) -> (i32, i32) {
+        glyph
+            .vector()
+            .or(self.vector.as_ref())
+            .copied()
+            .unwrap_or_else(|| ((self.max_bounds().width / 2) as i32, self.ascent() as i32))
+    }
+
+    /// Gets the default bounding box, or the union of the bounds of the
+    /// glyphs for fonts without one.
+    pub(crate) fn max_bounds(&self) -> BoundingBox {
+        self.bounds.unwrap_or_else(|| {
+            self.glyphs
+                .values()
+                .map(|glyph| *glyph.bounds())
+                .reduce(|bounds, other| bounds.union(&other))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Creates a bold variant by thickening every glyph by the given number of
+    /// bits.
+    ///
+    /// The bounds and widths are widened, and the `WEIGHT_NAME` property and
+    /// the weight in the XLFD name are set to `Bold`.
+    pub fn embolden(&self, offset: u32) -> Font {
+        let mut font = self.map_glyphs(|glyph| glyph.embolden(offset));
+
+        if let Some(bounds) = font.bounds.as_mut() {
+            bounds.width += offset;
+        }
+
+        // Marks without an advance keep it, so they still overlay the glyph
+        // before them.
+        if let Some((x, y)) = self.device_width.filter(|&(x, _)| x > 0) {
+            font.device_width = Some((x + offset as i32, y));
+            font.scalable_width = self
+                .scalable_width
+                .map(|(sx, sy)| (sx * (x + offset as i32) / x, sy));
+        }
+
+        font.properties
+            .insert("WEIGHT_NAME".into(), Property::String("Bold".into()));
+        font.set_xlfd(xlfd::WEIGHT_NAME, "Bold");
+
+        if let Some(Property::Integer(width)) = font.properties.get_mut("AVERAGE_WIDTH") {
+            *width += offset as i64 * 10;
+
+            let width = width.to_string();
+            font.set_xlfd(xlfd::AVERAGE_WIDTH, &width);
+        }
+
+        font
+    }
+
+    /// Creates an oblique variant by shearing every glyph around the baseline.
+    ///
+    /// The bounds are widened, and the `SLANT` property and the slant in the
+    /// XLFD name are set to `O`, or `RO` when slanting to the left. An upright
+    /// slant returns the font unchanged.
+    pub fn oblique(&self, slant: Slant) -> Font {
+        let step = match slant.step() {
+            Some(step) => step,
+            None => return self.clone(),
+        };
+
+        let mut font = self.map_glyphs(|glyph| glyph.shear(step));
+
+        if let Some(bounds) = font.bounds.as_mut() {
+            let top = bounds.y + bounds.height.saturating_sub(1) as i32;
+
+            bounds.width += (shift(top, step) - shift(bounds.y, step)).unsigned_abs();
+            bounds.x += shift(bounds.y, step).min(shift(top, step));
+        }
+
+        let slant = if step > 0 { "O" } else { "RO" };
+
+        font.properties
+            .insert("SLANT".into(), Property::String(slant.into()));
+        font.set_xlfd(xlfd::SLANT, slant);
+
+        font
+    }
+
+    /// Creates a bigger variant by enlarging every glyph by the given factor.
+    ///
+    /// The bounds, device widths, size and metric properties are scaled, and
+    /// the XLFD name is updated to match.
+    #[inline]
+    pub fn scale(&self, factor: u32) -> Font {
+        self.scale_with(factor, Scaler::Nearest)
+    }
+
+    /// Creates a bigger variant by enlarging every glyph by the given factor
+    /// using the given algorithm.
+    ///
+    /// Panics if the factor is not supported by the algorithm.
+    pub fn scale_with(&self, factor: u32, scaler: Scaler) -> Font {
+        let scale =
+            |value: Option<(i32, i32)>| value.map(|(x, y)| (x * factor as i32, y * factor as i32));
+        let mut font = self.map_glyphs(|glyph| glyph.scale_with(factor, scaler));
+
+        if let Some(bounds) = font.bounds.as_mut() {
+            bounds.width *= factor;
+            bounds.height *= factor;
+            bounds.x *= factor as i32;
+            bounds.y *= factor as i32;
+        }
+
+        if let Some(size) = font.size.as_mut() {
+            size.pt *= factor as u16;
+        }
+
+        font.device_width = scale(self.device_width);
+        font.alternate_device_width = scale(self.alternate_device_width);
+        font.vector = scale(self.vector);
+
+        for name in SCALED_PROPERTIES {
+            if let Some(Property::Integer(value)) = font.properties.get_mut(*name) {
+                *value *= factor as i64;
+            }
+        }
+
+        for &(name, field) in &[
+            ("PIXEL_SIZE", xlfd::PIXEL_SIZE),
+            ("POINT_SIZE", xlfd::POINT_SIZE),
+            ("AVERAGE_WIDTH", xlfd::AVERAGE_WIDTH),
+        ] {
+            if let Some(Property::Integer(value)) = font.properties.get(name) {
+                let value = value.to_string();
+                font.set_xlfd(field, &value);
+            }
+        }
+
+        font
+    }
+
+    /// Creates a companion font with an outline of the given radius around
+    /// every glyph, to be drawn at the same positions under this font.
+    pub fn outline(&self, radius: u32) -> Font {
+        let mut font = self.map_glyphs(|glyph| glyph.outline(radius));
+        font.grow(radius);
+
+        font
+    }
+
+    /// Creates a companion font with every glyph grown by the given radius, to
+    /// be drawn at the same positions under this font as a glow.
+    pub fn glow(&self, radius: u32) -> Font {
+        let mut font = self.map_glyphs(|glyph| glyph.dilate(radius));
+        font.grow(radius);
+
+        font
+    }
+
+    /// Creates a companion font with every glyph moved by the given offset,
+    /// with positive `y` going down, to be drawn at the same positions under
+    /// this font as a shadow.
+    pub fn shadow(&self, dx: i32, dy: i32) -> Font {
+        let mut font = self.map_glyphs(|glyph| glyph.translate(dx, dy));
+
+        if let Some(bounds) = font.bounds.as_mut() {
+            bounds.x += dx;
+            bounds.y -= dy;
+        }
+
+        font
+    }
+
+    /// Grows the default bounding box by the radius on every side.
+    fn grow(&mut self, radius: u32) {
+        if let Some(bounds) = self.bounds.as_mut() {
+            bounds.width += radius * 2;
+            bounds.height += radius * 2;
+            bounds.x -= radius as i32;
+            bounds.y -= radius as i32;
+        }
+    }
+
+    /// Creates a copy with every glyph trimmed to its set bits.
+    pub fn trim(&self) -> Font {
+        self.map_glyphs(Glyph::trim)
+    }
+
+    /// Creates a copy with every glyph padded to the default bounding box.
+    pub fn pad(&self) -> Font {
+        let bounds = self.bounds.unwrap_or_default();
+
+        self.map_glyphs(|glyph| glyph.pad(&bounds))
+    }
+
+    /// Replaces a field of the name, if it is a valid XLFD name.
+    fn set_xlfd(&mut self, field: usize, value: &str) {
+        if let Some(name) = self.name.as_ref().and_then(|n| xlfd::set(n, field, value)) {
+            self.name = Some(name);
+        }
+    }
+
+    /// Creates a copy with the given function applied to every glyph.
+    fn map_glyphs<F>(&self, mut f: F) -> Font
+    where
+        F: FnMut(&Glyph) -> Glyph,
+    {
+        let mut font = self.clone();
+
+        for glyph in font.glyphs.values_mut() {
+            *glyph = f(glyph);
+        }
+
+        font
+    }
+
     /// Gets the glyphs.
     #[inline]
-    pub fn glyphs(&self) -> &HashMap<char, Glyph> {
+    pub fn glyphs(&self) -> &BTreeMap<char, Glyph> {
         &self.glyphs
     }
 
     /// Gets a mutable reference to the glyphs.
     #[inline]
-    pub fn glyphs_mut(&mut self) -> &mut HashMap<char, Glyph> {
+    pub fn glyphs_mut(&mut self) -> &mut BTreeMap<char, Glyph> {
         &mut self.glyphs
     }
 }
@@ -1,5 +1,8 @@
-use crate::{Bitmap, BoundingBox, Direction};
-use std::ops::{Deref, DerefMut};
+use alloc::string::String;
+use core::ops::{Deref, DerefMut};
+
+use crate::bitmap::shift;
+use crate::{math, Ascii, Bitmap, BoundingBox, Direction, Error, Graymap, Scaler, SUBPIXELS};
 
 /// A font glyph.
 #[derive(Clone, Debug)]
@@ -9,13 +12,13 @@ pub struct Glyph {
 
     direction: Direction,
 
-    scalable_width: Option<(u32, u32)>,
-    device_width: Option<(u32, u32)>,
+    scalable_width: Option<(i32, i32)>,
+    device_width: Option<(i32, i32)>,
 
-    alternate_scalable_width: Option<(u32, u32)>,
-    alternate_device_width: Option<(u32, u32)>,
+    alternate_scalable_width: Option<(i32, i32)>,
+    alternate_device_width: Option<(i32, i32)>,
 
-    vector: Option<(u32, u32)>,
+    vector: Option<(i32, i32)>,
 
     bounds: Option<BoundingBox>,
     map: Bitmap,
@@ -94,7 +97,7 @@ impl Glyph {
     /// Gets the name.
     #[inline]
     pub fn name(&self) -> &str {
-        &self.name.as_ref().unwrap().as_ref()
+        self.name.as_ref().unwrap().as_ref()
     }
 
     /// Sets the name.
@@ -129,61 +132,61 @@ impl Glyph {
 
     /// Gets the scalable width.
     #[inline]
-    pub fn scalable_width(&self) -> Option<&(u32, u32)> {
+    pub fn scalable_width(&self) -> Option<&(i32, i32)> {
         self.scalable_width.as_ref()
     }
 
     /// Sets the scalable width.
     #[inline]
-    pub fn set_scalable_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_scalable_width(&mut self, value: Option<(i32, i32)>) {
         self.scalable_width = value;
     }
 
     /// Gets the device width.
     #[inline]
-    pub fn device_width(&self) -> Option<&(u32, u32)> {
+    pub fn device_width(&self) -> Option<&(i32, i32)> {
         self.device_width.as_ref()
     }
 
     /// Sets the device width.
     #[inline]
-    pub fn set_device_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_device_width(&mut self, value: Option<(i32, i32)>) {
         self.device_width = value;
     }
 
     /// Gets the alternate scalable width.
     #[inline]
-    pub fn alternate_scalable_width(&self) -> Option<&(u32, u32)> {
+    pub fn alternate_scalable_width(&self) -> Option<&(i32, i32)> {
         self.alternate_scalable_width.as_ref()
     }
 
     /// Sets the alternate scalable width.
     #[inline]
-    pub fn set_alternate_scalable_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_alternate_scalable_width(&mut self, value: Option<(i32, i32)>) {
         self.alternate_scalable_width = value;
     }
 
     /// Gets the alternate device width.
     #[inline]
-    pub fn alternate_device_width(&self) -> Option<&(u32, u32)> {
+    pub fn alternate_device_width(&self) -> Option<&(i32, i32)> {
         self.alternate_device_width.as_ref()
     }
 
     /// Sets the alternate device width.
     #[inline]
-    pub fn set_alternate_device_width(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_alternate_device_width(&mut self, value: Option<(i32, i32)>) {
         self.alternate_device_width = value;
     }
 
     /// Gets the offset vector.
     #[inline]
-    pub fn vector(&self) -> Option<&(u32, u32)> {
+    pub fn vector(&self) -> Option<&(i32, i32)> {
         self.vector.as_ref()
     }
 
     /// Sets the offset vector.
     #[inline]
-    pub fn set_vector(&mut self, value: Option<(u32, u32)>) {
+    pub fn set_vector(&mut self, value: Option<(i32, i32)>) {
         self.vector = value;
     }
 
@@ -211,9 +214,315 @@ impl Glyph {
         self.map = map;
     }
 
+    /// Mirrors the glyph horizontally within its device width.
+    ///
+    /// Without a device width the glyph is mirrored around the origin.
+    pub fn flip_horizontal(&self) -> Glyph {
+        let advance = self.device_width.map_or(0, |(x, _)| x);
+
+        self.transform(self.map.flip_horizontal(), keep, |bbx| BoundingBox {
+            x: advance - (bbx.x + bbx.width as i32),
+            ..bbx
+        })
+    }
+
+    /// Mirrors the glyph vertically around the baseline.
+    ///
+    /// The vertical components of the widths are negated.
+    pub fn flip_vertical(&self) -> Glyph {
+        self.transform(
+            self.map.flip_vertical(),
+            |(x, y)| (x, -y),
+            |bbx| BoundingBox {
+                y: -(bbx.y + bbx.height as i32),
+                ..bbx
+            },
+        )
+    }
+
+    /// Reflects the glyph across the anti-diagonal going through the origin.
+    ///
+    /// The widths are reflected along with the bitmap, so `(x, y)` becomes
+    /// `(-y, -x)`.
+    pub fn transpose(&self) -> Glyph {
+        self.transform(
+            self.map.transpose(),
+            |(x, y)| (-y, -x),
+            |bbx| BoundingBox {
+                width: bbx.height,
+                height: bbx.width,
+
+                x: -(bbx.y + bbx.height as i32),
+                y: -(bbx.x + bbx.width as i32),
+            },
+        )
+    }
+
+    /// Rotates the glyph clockwise by 90 degrees around the origin.
+    ///
+    /// The widths are rotated along with the bitmap, so `(x, y)` becomes
+    /// `(y, -x)`.
+    pub fn rotate_90(&self) -> Glyph {
+        self.transform(
+            self.map.rotate_90(),
+            |(x, y)| (y, -x),
+            |bbx| BoundingBox {
+                width: bbx.height,
+                height: bbx.width,
+
+                x: bbx.y,
+                y: -(bbx.x + bbx.width as i32),
+            },
+        )
+    }
+
+    /// Rotates the glyph by 180 degrees around the origin.
+    ///
+    /// The widths are rotated along with the bitmap, so `(x, y)` becomes
+    /// `(-x, -y)`.
+    pub fn rotate_180(&self) -> Glyph {
+        self.transform(
+            self.map.rotate_180(),
+            |(x, y)| (-x, -y),
+            |bbx| BoundingBox {
+                x: -(bbx.x + bbx.width as i32),
+                y: -(bbx.y + bbx.height as i32),
+                ..bbx
+            },
+        )
+    }
+
+    /// Rotates the glyph clockwise by 270 degrees around the origin.
+    ///
+    /// The widths are rotated along with the bitmap, so `(x, y)` becomes
+    /// `(-y, x)`.
+    pub fn rotate_270(&self) -> Glyph {
+        self.transform(
+            self.map.rotate_270(),
+            |(x, y)| (-y, x),
+            |bbx| BoundingBox {
+                width: bbx.height,
+                height: bbx.width,
+
+                x: -(bbx.y + bbx.height as i32),
+                y: bbx.x,
+            },
+        )
+    }
+
+    /// Moves the glyph relative to the origin, with positive `y` going down
+    /// like `Bitmap::translate` and `shadow`.
+    ///
+    /// Only the bounds are changed, the bitmap is kept as is.
+    pub fn translate(&self, dx: i32, dy: i32) -> Glyph {
+        self.transform(self.map.clone(), keep, |bbx| BoundingBox {
+            x: bbx.x + dx,
+            y: bbx.y - dy,
+            ..bbx
+        })
+    }
+
+    /// Thickens the strokes by the given number of bits, widening the bounds
+    /// and the widths accordingly.
+    pub fn embolden(&self, offset: u32) -> Glyph {
+        let mut glyph = self.transform(self.map.embolden(offset), keep, |bbx| BoundingBox {
+            width: bbx.width + offset,
+            ..bbx
+        });
+
+        // Marks without an advance keep it, so they still overlay the glyph
+        // before them.
+        if let Some((x, y)) = self.device_width.filter(|&(x, _)| x > 0) {
+            glyph.device_width = Some((x + offset as i32, y));
+            glyph.scalable_width = self
+                .scalable_width
+                .map(|(sx, sy)| (sx * (x + offset as i32) / x, sy));
+        }
+
+        glyph
+    }
+
+    /// Slants the glyph by shifting rows one bit for every `step` rows,
+    /// keeping the baseline fixed.
+    ///
+    /// A positive `step` slants to the right and a negative one to the left,
+    /// a `step` of 0 keeps the glyph as is.
+    pub fn shear(&self, step: i32) -> Glyph {
+        let bottom = self.bounds.map_or(0, |bbx| bbx.y);
+        let map = self.map.shear(step, bottom);
+        let grown = map.width() - self.map.width();
+
+        self.transform(map, keep, |bbx| BoundingBox {
+            width: bbx.width + grown,
+            x: bbx.x
+                + shift(bbx.y, step).min(shift(bbx.y + bbx.height.saturating_sub(1) as i32, step)),
+            ..bbx
+        })
+    }
+
+    /// Enlarges the glyph by the given factor, scaling the bounds, the device
+    /// widths and the offset vector along with the bitmap.
+    #[inline]
+    pub fn scale(&self, factor: u32) -> Glyph {
+        self.scale_with(factor, Scaler::Nearest)
+    }
+
+    /// Enlarges the glyph by the given factor using the given algorithm.
+    ///
+    /// Panics if the factor is not supported by the algorithm.
+    pub fn scale_with(&self, factor: u32, scaler: Scaler) -> Glyph {
+        let scale =
+            |value: Option<(i32, i32)>| value.map(|(x, y)| (x * factor as i32, y * factor as i32));
+        let map = scaler.scale(&self.map, factor);
+        let mut glyph = self.transform(map, keep, |bbx| BoundingBox {
+            width: bbx.width * factor,
+            height: bbx.height * factor,
+
+            x: bbx.x * factor as i32,
+            y: bbx.y * factor as i32,
+        });
+
+        glyph.device_width = scale(self.device_width);
+        glyph.alternate_device_width = scale(self.alternate_device_width);
+        glyph.vector = scale(self.vector);
+
+        glyph
+    }
+
+    /// Creates a coverage map of the glyph scaled by the given factor and moved
+    /// by the given offsets in subpixels, with positive `y` going down.
+    ///
+    /// The returned bounds place the coverage map relative to the origin.
+    ///
+    /// Fails with `Error::TooLarge` if the scaled map does not fit in memory.
+    /// Panics if the scale is not positive.
+    pub fn coverage(&self, scale: f32, dx: i32, dy: i32) -> Result<(Graymap, BoundingBox), Error> {
+        let bbx = self.bounds.unwrap_or_default();
+        let subpixels = SUBPIXELS as f32;
+
+        // Edges of the scaled glyph in subpixels, with positive `y` going up.
+        let left = math::round(bbx.x as f32 * scale * subpixels) as i32 + dx;
+        let top = math::round((bbx.y + bbx.height as i32) as f32 * scale * subpixels) as i32 - dy;
+
+        let x = left.div_euclid(SUBPIXELS);
+        let y = -(-top).div_euclid(SUBPIXELS);
+
+        let map =
+            Graymap::from_bitmap(&self.map, scale, left - x * SUBPIXELS, y * SUBPIXELS - top)?;
+        let bounds = BoundingBox {
+            width: map.width(),
+            height: map.height(),
+
+            x,
+            y: y - map.height() as i32,
+        };
+
+        Ok((map, bounds))
+    }
+
+    /// Grows the strokes by the given radius, growing the bounds on every side
+    /// while keeping the widths.
+    pub fn dilate(&self, radius: u32) -> Glyph {
+        self.transform(self.map.dilate(radius), keep, |bbx| grow(bbx, radius))
+    }
+
+    /// Shrinks the strokes by the given radius.
+    pub fn erode(&self, radius: u32) -> Glyph {
+        self.transform(self.map.erode(radius), keep, |bbx| bbx)
+    }
+
+    /// Creates an outline of the given radius around the strokes, growing the
+    /// bounds on every side while keeping the widths.
+    pub fn outline(&self, radius: u32) -> Glyph {
+        self.transform(self.map.outline(radius), keep, |bbx| grow(bbx, radius))
+    }
+
+    /// Adds a copy moved by the given offset behind the strokes, with positive
+    /// `y` going down, growing the bounds to fit both while keeping the widths.
+    pub fn shadow(&self, dx: i32, dy: i32) -> Glyph {
+        self.transform(self.map.shadow(dx, dy), keep, |bbx| BoundingBox {
+            width: bbx.width + dx.unsigned_abs(),
+            height: bbx.height + dy.unsigned_abs(),
+
+            x: bbx.x + dx.min(0),
+            y: bbx.y - dy.max(0),
+        })
+    }
+
+    /// Shrinks the bitmap to the set bits, adjusting the offsets so that the
+    /// glyph renders the same.
+    ///
+    /// Glyphs without any set bits end up with an empty bounding box.
+    pub fn trim(&self) -> Glyph {
+        let height = self.map.height();
+
+        match self.map.ink_bounds() {
+            Some((x, y, width, ink_height)) => self.transform(
+                self.map.crop(x as i32, y as i32, width, ink_height),
+                keep,
+                |bbx| BoundingBox {
+                    width,
+                    height: ink_height,
+
+                    x: bbx.x + x as i32,
+                    y: bbx.y + (height - y - ink_height) as i32,
+                },
+            ),
+
+            None => self.transform(Bitmap::new(0, 0), keep, |_| BoundingBox::default()),
+        }
+    }
+
+    /// Grows the bitmap to cover the given bounds, adjusting the offsets so
+    /// that the glyph renders the same.
+    ///
+    /// Bits outside of the given bounds are kept.
+    pub fn pad(&self, bounds: &BoundingBox) -> Glyph {
+        let current = self.bounds.unwrap_or_default();
+        let padded = current.union(bounds);
+
+        self.transform(
+            self.map.crop(
+                padded.x - current.x,
+                (current.y + current.height as i32) - (padded.y + padded.height as i32),
+                padded.width,
+                padded.height,
+            ),
+            keep,
+            |_| padded,
+        )
+    }
+
+    /// Creates a copy with the given bitmap, mapping the widths and the bounds
+    /// with the given functions.
+    fn transform<F>(&self, map: Bitmap, widths: fn((i32, i32)) -> (i32, i32), bounds: F) -> Glyph
+    where
+        F: FnOnce(BoundingBox) -> BoundingBox,
+    {
+        Glyph {
+            scalable_width: self.scalable_width.map(widths),
+            device_width: self.device_width.map(widths),
+
+            alternate_scalable_width: self.alternate_scalable_width.map(widths),
+            alternate_device_width: self.alternate_device_width.map(widths),
+
+            bounds: self.bounds.map(bounds),
+            map,
+
+            ..self.clone()
+        }
+    }
+
+    /// Creates a configurable ASCII art rendering of the glyph, which can also
+    /// show the baseline and the origin.
+    #[inline]
+    pub fn ascii(&self) -> Ascii<'_> {
+        Ascii::new(&self.map, self.bounds)
+    }
+
     /// Create an iterator over the pixels which will yield `((x, y), value)`.
     #[inline]
-    pub fn pixels(&self) -> PixelIter {
+    pub fn pixels(&self) -> PixelIter<'_> {
         PixelIter {
             x: 0,
             y: 0,
@@ -223,6 +532,22 @@ impl Glyph {
     }
 }
 
+/// Keeps the widths as they are.
+fn keep(widths: (i32, i32)) -> (i32, i32) {
+    widths
+}
+
+/// Grows the bounding box by the radius on every side.
+fn grow(bbx: BoundingBox, radius: u32) -> BoundingBox {
+    BoundingBox {
+        width: bbx.width + radius * 2,
+        height: bbx.height + radius * 2,
+
+        x: bbx.x - radius as i32,
+        y: bbx.y - radius as i32,
+    }
+}
+
 impl Deref for Glyph {
     type Target = Bitmap;
 
@@ -282,3 +607,160 @@ impl<'a> Iterator for PixelIter<'a> {
 }
 
 impl<'a> ExactSizeIterator for PixelIter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, BoundingBox, Glyph};
+
+    fn glyph() -> Glyph {
+        let mut map = Bitmap::new(3, 5);
+        map.set(0, 0, true);
+        map.set(2, 4, true);
+
+        let mut glyph = Glyph::new("a", 'a');
+        glyph.set_bounds(BoundingBox {
+            width: 3,
+            height: 5,
+            x: 1,
+            y: -2,
+        });
+        glyph.set_scalable_width(Some((500, 0)));
+        glyph.set_device_width(Some((6, 0)));
+        glyph.set_map(map);
+
+        glyph
+    }
+
+    fn bounds(width: u32, height: u32, x: i32, y: i32) -> BoundingBox {
+        BoundingBox {
+            width,
+            height,
+            x,
+            y,
+        }
+    }
+
+    #[test]
+    fn flip_horizontal() {
+        let input = glyph();
+        let output = input.flip_horizontal();
+
+        assert_eq!(output.map(), &input.map().flip_horizontal());
+        assert_eq!(output.bounds(), &bounds(3, 5, 2, -2));
+        assert_eq!(output.device_width(), Some(&(6, 0)));
+
+        let mut input = glyph();
+        input.set_device_width(None);
+
+        assert_eq!(input.flip_horizontal().bounds(), &bounds(3, 5, -4, -2));
+    }
+
+    #[test]
+    fn flip_vertical() {
+        let input = glyph();
+        let output = input.flip_vertical();
+
+        assert_eq!(output.map(), &input.map().flip_vertical());
+        assert_eq!(output.bounds(), &bounds(3, 5, 1, -3));
+        assert_eq!(output.device_width(), Some(&(6, 0)));
+
+        let mut input = glyph();
+        input.set_alternate_device_width(Some((0, -8)));
+
+        assert_eq!(
+            input.flip_vertical().alternate_device_width(),
+            Some(&(0, 8))
+        );
+    }
+
+    #[test]
+    fn transpose() {
+        let input = glyph();
+        let output = input.transpose();
+
+        assert_eq!(output.map(), &input.map().transpose());
+        assert_eq!(output.bounds(), &bounds(5, 3, -3, -4));
+        assert_eq!(output.device_width(), Some(&(0, -6)));
+        assert_eq!(output.scalable_width(), Some(&(0, -500)));
+    }
+
+    #[test]
+    fn rotate_90() {
+        let input = glyph();
+        let output = input.rotate_90();
+
+        assert_eq!(output.map(), &input.map().rotate_90());
+        assert_eq!(output.bounds(), &bounds(5, 3, -2, -4));
+        assert_eq!(output.device_width(), Some(&(0, -6)));
+        assert_eq!(output.scalable_width(), Some(&(0, -500)));
+    }
+
+    #[test]
+    fn rotate_180() {
+        let input = glyph();
+        let output = input.rotate_180();
+
+        assert_eq!(output.map(), &input.map().rotate_180());
+        assert_eq!(output.bounds(), &bounds(3, 5, -4, -3));
+        assert_eq!(output.device_width(), Some(&(-6, 0)));
+        assert_eq!(output.scalable_width(), Some(&(-500, 0)));
+    }
+
+    #[test]
+    fn rotate_270() {
+        let input = glyph();
+        let output = input.rotate_270();
+
+        assert_eq!(output.map(), &input.map().rotate_270());
+        assert_eq!(output.bounds(), &bounds(5, 3, -3, 1));
+        assert_eq!(output.device_width(), Some(&(0, 6)));
+        assert_eq!(output.scalable_width(), Some(&(0, 500)));
+    }
+
+    #[test]
+    fn rotations_compose() {
+        let input = glyph();
+
+        for (output, expected) in [
+            (input.rotate_90().rotate_90(), input.rotate_180()),
+            (input.rotate_90().rotate_270(), input.clone()),
+            (input.rotate_180().rotate_180(), input.clone()),
+            (input.transpose().transpose(), input.clone()),
+        ] {
+            assert_eq!(output.map(), expected.map());
+            assert_eq!(output.bounds(), expected.bounds());
+            assert_eq!(output.device_width(), expected.device_width());
+            assert_eq!(output.scalable_width(), expected.scalable_width());
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let input = glyph();
+        let output = input.translate(2, 3);
+
+        assert_eq!(output.map(), input.map());
+        assert_eq!(output.bounds(), &bounds(3, 5, 3, -5));
+        assert_eq!(output.device_width(), Some(&(6, 0)));
+    }
+
+    #[test]
+    fn embolden() {
+        let input = glyph();
+        let output = input.embolden(1);
+
+        assert_eq!(output.map(), &input.map().embolden(1));
+        assert_eq!(output.bounds(), &bounds(4, 5, 1, -2));
+        assert_eq!(output.device_width(), Some(&(7, 0)));
+        assert_eq!(output.scalable_width(), Some(&(583, 0)));
+
+        let mut input = glyph();
+        input.set_device_width(Some((0, 0)));
+        input.set_scalable_width(Some((0, 0)));
+        let output = input.embolden(1);
+
+        assert_eq!(output.bounds(), &bounds(4, 5, 1, -2));
+        assert_eq!(output.device_width(), Some(&(0, 0)));
+        assert_eq!(output.scalable_width(), Some(&(0, 0)));
+    }
+}
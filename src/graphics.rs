@@ -0,0 +1,168 @@
+use embedded_graphics::pixelcolor::PixelColor;
+use embedded_graphics::prelude::{DrawTarget, Point, Size};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::{CharacterStyle, TextMetrics, TextRenderer};
+use embedded_graphics::text::Baseline;
+
+use crate::{Font, Renderer, Target};
+
+/// A character style drawing text with a font on `embedded-graphics` draw
+/// targets.
+///
+/// Every line is as tall as the line height of the font, and the background,
+/// when set, covers the advance of the text.
+#[derive(Clone, Copy, Debug)]
+pub struct BdfTextStyle<'a, C> {
+    font: &'a Font,
+
+    text_color: Option<C>,
+    background_color: Option<C>,
+}
+
+impl<'a, C: PixelColor> BdfTextStyle<'a, C> {
+    /// Creates a style drawing text with the given font and color.
+    #[inline]
+    pub fn new(font: &'a Font, text_color: C) -> Self {
+        BdfTextStyle {
+            font,
+
+            text_color: Some(text_color),
+            background_color: None,
+        }
+    }
+
+    /// Gets the font.
+    #[inline]
+    pub fn font(&self) -> &'a Font {
+        self.font
+    }
+
+    /// Gets the distance from the top of a line to the position of the
+    /// baseline.
+    fn offset(&self, baseline: Baseline) -> i32 {
+        let height = self.font.line_height() as i32;
+
+        match baseline {
+            Baseline::Top => 0,
+
+            Baseline::Bottom => (height - 1).max(0),
+
+            Baseline::Middle => (height - 1).max(0) / 2,
+
+            Baseline::Alphabetic => self.font.ascent() as i32 - 1,
+        }
+    }
+
+    /// Gets the area covered by a line of the given width.
+    fn area(&self, width: u32, position: Point, baseline: Baseline) -> Rectangle {
+        Rectangle::new(
+            position - Point::new(0, self.offset(baseline)),
+            Size::new(width, self.font.line_height()),
+        )
+    }
+}
+
+impl<C: PixelColor> TextRenderer for BdfTextStyle<'_, C> {
+    type Color = C;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let width = self.font.text_width(text);
+        let area = self.area(width, position, baseline);
+
+        if let Some(color) = self.background_color {
+            target.fill_solid(&area, color)?;
+        }
+
+        // The glyphs are drawn as filled spans, which many displays draw
+        // faster than single pixels.
+        if let Some(color) = self.text_color {
+            let mut fills = Fills {
+                target,
+                color,
+                result: Ok(()),
+            };
+            Renderer::new(self.font).draw(text, area.top_left.x, area.top_left.y, &mut fills);
+
+            fills.result?;
+        }
+
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if let Some(color) = self.background_color {
+            target.fill_solid(&self.area(width, position, baseline), color)?;
+        }
+
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let width = self.font.text_width(text);
+
+        TextMetrics {
+            bounding_box: self.area(width, position, baseline),
+            next_position: position + Point::new(width as i32, 0),
+        }
+    }
+
+    #[inline]
+    fn line_height(&self) -> u32 {
+        self.font.line_height()
+    }
+}
+
+impl<C: PixelColor> CharacterStyle for BdfTextStyle<'_, C> {
+    type Color = C;
+
+    #[inline]
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.text_color = text_color;
+    }
+
+    #[inline]
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+}
+
+/// Fills the spans drawn by a renderer on a draw target, keeping the first
+/// error and skipping the spans after it.
+struct Fills<'a, D: DrawTarget> {
+    target: &'a mut D,
+    color: D::Color,
+    result: Result<(), D::Error>,
+}
+
+impl<D: DrawTarget> Target for Fills<'_, D> {
+    #[inline]
+    fn draw(&mut self, x: i32, y: i32) {
+        self.span(x, y, 1);
+    }
+
+    #[inline]
+    fn span(&mut self, x: i32, y: i32, width: u32) {
+        if self.result.is_ok() {
+            let area = Rectangle::new(Point::new(x, y), Size::new(width, 1));
+            self.result = self.target.fill_solid(&area, self.color);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{math, Bitmap, Error};
+
+/// The number of steps in a pixel for fixed-point subpixel offsets.
+pub const SUBPIXELS: i32 = 256;
+
+/// An 8-bit coverage map of a glyph.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Graymap {
+    width: u32,
+    height: u32,
+
+    data: Vec<u8>,
+}
+
+impl Default for Graymap {
+    #[inline]
+    fn default() -> Self {
+        Graymap {
+            width: 0,
+            height: 0,
+
+            data: Vec::new(),
+        }
+    }
+}
+
+impl Graymap {
+    /// Creates an empty coverage map of the given size.
+    ///
+    /// Fails with `Error::TooLarge` if the number of pixels overflows.
+    pub fn new(width: u32, height: u32) -> Result<Self, Error> {
+        let size = width
+            .checked_mul(height)
+            .and_then(|size| usize::try_from(size).ok())
+            .ok_or(Error::TooLarge { width, height })?;
+
+        Ok(Graymap {
+            width,
+            height,
+
+            data: vec![0; size],
+        })
+    }
+
+    /// Creates a coverage map from the bitmap scaled by the given factor and
+    /// moved by the given offsets in subpixels, with positive `y` going down.
+    ///
+    /// Every pixel gets the area of the bitmap it covers, as a box filter.
+    /// The map starts at the origin, so bits moved above or left of it by
+    /// negative offsets are clipped.
+    ///
+    /// Fails with `Error::TooLarge` if the scaled map does not fit in memory.
+    /// Panics if the scale is not positive.
+    pub fn from_bitmap(map: &Bitmap, scale: f32, dx: i32, dy: i32) -> Result<Self, Error> {
+        assert!(scale > 0.0, "invalid coverage scale");
+
+        let left = dx as f32 / SUBPIXELS as f32;
+        let top = dy as f32 / SUBPIXELS as f32;
+        let width = math::ceil(map.width() as f32 * scale + left).max(0.0) as u32;
+        let height = math::ceil(map.height() as f32 * scale + top).max(0.0) as u32;
+
+        let mut result = Graymap::new(width, height)?;
+
+        for y in 0..height {
+            let y0 = ((y as f32 - top) / scale).max(0.0);
+            let y1 = ((y as f32 + 1.0 - top) / scale).min(map.height() as f32);
+
+            for x in 0..width {
+                let x0 = ((x as f32 - left) / scale).max(0.0);
+                let x1 = ((x as f32 + 1.0 - left) / scale).min(map.width() as f32);
+
+                let mut area = 0.0;
+
+                for row in math::floor(y0) as u32..(math::ceil(y1) as u32).min(map.height()) {
+                    let covered_y = y1.min(row as f32 + 1.0) - y0.max(row as f32);
+
+                    for column in math::floor(x0) as u32..(math::ceil(x1) as u32).min(map.width()) {
+                        if map.get(column, row) {
+                            let covered_x = x1.min(column as f32 + 1.0) - x0.max(column as f32);
+
+                            area += covered_x * covered_y;
+                        }
+                    }
+                }
+
+                let value = math::round(area * scale * scale * 255.0).clamp(0.0, 255.0);
+                result.set(x, y, value as u8);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Gets the width.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the coverage of a pixel.
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> u8 {
+        if y >= self.height || x >= self.width {
+            panic!("out of bounds");
+        }
+
+        self.data[(y * self.width + x) as usize]
+    }
+
+    /// Sets the coverage of a pixel.
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, value: u8) {
+        if y >= self.height || x >= self.width {
+            panic!("out of bounds");
+        }
+
+        self.data[(y * self.width + x) as usize] = value;
+    }
+
+    /// Gets the coverage values row by row.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, Error, Graymap, SUBPIXELS};
+
+    #[test]
+    fn identity() {
+        let mut map = Bitmap::new(2, 1);
+        map.set(1, 0, true);
+
+        assert_eq!(
+            Graymap::from_bitmap(&map, 1.0, 0, 0).unwrap().data(),
+            &[0, 255]
+        );
+    }
+
+    #[test]
+    fn downsample() {
+        let mut map = Bitmap::new(4, 2);
+        map.set(0, 0, true);
+        map.set(1, 0, true);
+        map.set(2, 0, true);
+
+        let gray = Graymap::from_bitmap(&map, 0.5, 0, 0).unwrap();
+
+        assert_eq!((gray.width(), gray.height()), (2, 1));
+        assert_eq!(gray.data(), &[128, 64]);
+    }
+
+    #[test]
+    fn subpixel_offset() {
+        let mut map = Bitmap::new(1, 1);
+        map.set(0, 0, true);
+
+        let gray = Graymap::from_bitmap(&map, 1.0, SUBPIXELS / 4, 0).unwrap();
+
+        assert_eq!(gray.data(), &[191, 64]);
+    }
+
+    #[test]
+    fn negative_offset() {
+        let mut map = Bitmap::new(2, 2);
+        map.set(0, 0, true);
+        map.set(1, 1, true);
+
+        let gray = Graymap::from_bitmap(&map, 1.0, -SUBPIXELS / 4, -SUBPIXELS).unwrap();
+
+        assert_eq!((gray.width(), gray.height()), (2, 1));
+        assert_eq!(gray.data(), &[64, 191]);
+
+        let gray = Graymap::from_bitmap(&map, 0.5, -SUBPIXELS / 2, 0).unwrap();
+
+        assert_eq!((gray.width(), gray.height()), (1, 1));
+        assert_eq!(gray.data(), &[64]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_scale() {
+        let _ = Graymap::from_bitmap(&Bitmap::new(1, 1), 0.0, 0, 0);
+    }
+
+    #[test]
+    fn too_large() {
+        assert!(matches!(
+            Graymap::new(u32::MAX, 2),
+            Err(Error::TooLarge {
+                width: u32::MAX,
+                height: 2
+            })
+        ));
+        assert_eq!(Graymap::new(0, u32::MAX).unwrap().data(), &[]);
+    }
+}
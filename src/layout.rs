@@ -0,0 +1,839 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{fallback, BoundingBox, Font, FontStack, Glyph};
+
+/// The horizontal alignment of lines.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Align {
+    /// Lines start at the edge their paragraph starts from, the left edge
+    /// unless the paragraph is right-to-left.
+    Start,
+
+    /// Lines end at the edge their paragraph ends at, the right edge unless
+    /// the paragraph is right-to-left.
+    End,
+
+    /// Lines start at the left edge.
+    Left,
+
+    /// Lines are centered.
+    Center,
+
+    /// Lines end at the right edge.
+    Right,
+}
+
+impl Default for Align {
+    #[inline]
+    fn default() -> Self {
+        Align::Start
+    }
+}
+
+/// The direction lines are written in.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Orientation {
+    /// Lines go left to right, stacked top to bottom.
+    Horizontal,
+
+    /// Lines go top to bottom as columns, stacked right to left, advancing by
+    /// `DWIDTH1` and positioned by `VVECTOR`.
+    Vertical,
+}
+
+impl Default for Orientation {
+    #[inline]
+    fn default() -> Self {
+        Orientation::Horizontal
+    }
+}
+
+/// A glyph placed by a `Layout`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Positioned {
+    /// The index of the character in the text.
+    pub index: usize,
+
+    /// The codepoint of the glyph.
+    pub codepoint: char,
+
+    /// The index of the font the glyph comes from, always `0` unless laid out
+    /// with a `FontStack`.
+    pub font: usize,
+
+    /// The horizontal position of the origin.
+    pub x: i32,
+
+    /// The vertical position of the origin, with positive `y` going down.
+    pub y: i32,
+}
+
+/// A line of glyphs placed by a `Layout`.
+///
+/// For vertical layouts the position is the top of the column on the line
+/// going through the vertical origins.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Run {
+    /// The horizontal position of the left edge of the line, trailing spaces
+    /// of right-to-left lines hanging before it.
+    pub x: i32,
+
+    /// The vertical position of the start of the line, on the baseline, with
+    /// positive `y` going down.
+    pub y: i32,
+
+    /// The advance length of the line, without trailing spaces.
+    pub width: u32,
+
+    /// The glyphs in the line.
+    pub glyphs: Vec<Positioned>,
+}
+
+/// Lays out text in a box, relative to its top left corner.
+///
+/// Glyphs are advanced by their `DWIDTH` and lines are broken at newlines and,
+/// when a width is set, wrapped at spaces, breaking words that do not fit on
+/// their own line.
+///
+/// In vertical layouts the width and the alignment apply to the columns, and
+/// the line height is the distance between two columns, defaulting to the width
+/// of the default bounding box, or of the bounds of all the glyphs for fonts
+/// without one.
+///
+/// With a `FontStack` every character uses the first font that has a glyph for
+/// it, with the glyphs of every font on the same baseline, and the ascent and
+/// descent of lines being the largest of the fonts.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout<'a> {
+    pub(crate) fonts: &'a [Font],
+
+    // The default glyph of the first font, or the bounds of the boxes drawn
+    // for missing characters, looked up once.
+    default: Option<&'a Glyph>,
+    missing: BoundingBox,
+
+    width: Option<u32>,
+    align: Align,
+    line_height: Option<u32>,
+    orientation: Orientation,
+}
+
+impl<'a> Layout<'a> {
+    /// Creates a layout for the given font.
+    #[inline]
+    pub fn new(font: &'a Font) -> Self {
+        Layout::with_fonts(core::slice::from_ref(font))
+    }
+
+    /// Creates a layout for the given font stack.
+    #[inline]
+    pub fn stack(fonts: &'a FontStack) -> Self {
+        Layout::with_fonts(fonts.fonts())
+    }
+
+    #[inline]
+    fn with_fonts(fonts: &'a [Font]) -> Self {
+        let default = fonts[0].default_glyph();
+
+        Layout {
+            fonts,
+
+            default,
+            missing: match default {
+                Some(_) => BoundingBox::default(),
+                None => fonts[0].max_bounds(),
+            },
+
+            width: None,
+            align: Align::Start,
+            line_height: None,
+            orientation: Orientation::Horizontal,
+        }
+    }
+
+    /// Sets the width lines are wrapped at.
+    #[inline]
+    pub fn width(mut self, value: u32) -> Self {
+        self.width = Some(value);
+        self
+    }
+
+    /// Sets the horizontal alignment of the lines.
+    #[inline]
+    pub fn align(mut self, value: Align) -> Self {
+        self.align = value;
+        self
+    }
+
+    /// Sets the distance between the baselines of two lines.
+    #[inline]
+    pub fn line_height(mut self, value: u32) -> Self {
+        self.line_height = Some(value);
+        self
+    }
+
+    /// Sets the direction lines are written in.
+    #[inline]
+    pub fn orientation(mut self, value: Orientation) -> Self {
+        self.orientation = value;
+        self
+    }
+
+    /// Gets the largest distance from the baseline to the top of a line.
+    pub(crate) fn ascent(&self) -> u32 {
+        self.fonts.iter().map(Font::ascent).max().unwrap_or(0)
+    }
+
+    /// Gets the largest distance from the baseline to the bottom of a line.
+    pub(crate) fn descent(&self) -> u32 {
+        self.fonts.iter().map(Font::descent).max().unwrap_or(0)
+    }
+
+    /// Gets the distance between the baselines of two lines.
+    pub(crate) fn spacing(&self) -> u32 {
+        self.line_height.unwrap_or_else(|| match self.orientation {
+            Orientation::Horizontal => self.ascent() + self.descent(),
+
+            Orientation::Vertical => self
+                .fonts
+                .iter()
+                .map(|font| font.max_bounds().width)
+                .max()
+                .unwrap_or(0),
+        })
+    }
+
+    /// Checks if any font has a glyph for the character.
+    pub(crate) fn contains(&self, codepoint: char) -> bool {
+        self.fonts
+            .iter()
+            .any(|font| font.glyphs().contains_key(&codepoint))
+    }
+
+    /// Gets the index of the first font with a glyph for the character and the
+    /// glyph, or the fallback glyph of the first font.
+    pub(crate) fn glyph(&self, codepoint: char) -> (usize, Cow<'a, Glyph>) {
+        let fonts = self.fonts;
+
+        match fonts
+            .iter()
+            .enumerate()
+            .find_map(|(index, font)| Some((index, font.glyphs().get(&codepoint)?)))
+        {
+            Some((index, glyph)) => (index, Cow::Borrowed(glyph)),
+
+            None => match self.default {
+                Some(glyph) => (0, Cow::Borrowed(glyph)),
+
+                None => (
+                    0,
+                    Cow::Owned(fallback::missing_glyph(self.missing, codepoint)),
+                ),
+            },
+        }
+    }
+
+    /// Gets the size of the box containing the laid out lines.
+    pub(crate) fn size(&self, runs: &[Run]) -> (u32, u32) {
+        let length = self
+            .width
+            .unwrap_or_else(|| runs.iter().map(|run| run.width).max().unwrap_or(0));
+        let across = runs.len() as u32 * self.spacing();
+
+        match self.orientation {
+            Orientation::Horizontal => (length, across),
+
+            Orientation::Vertical => (across, length),
+        }
+    }
+
+    /// Lays out the text into lines of positioned glyphs.
+    ///
+    /// Characters without a glyph use the fallback glyph of the font, control
+    /// characters are skipped, and combining marks are drawn over the preceding
+    /// glyph without advancing. With the `normalization` feature characters
+    /// without a glyph are decomposed first.
+    ///
+    /// With the `bidi` feature horizontal lines are reordered following the
+    /// Unicode Bidirectional Algorithm, and right-to-left characters advance by
+    /// their alternate metrics when the font has them.
+    pub fn runs(&self, text: &str) -> Vec<Run> {
+        let fonts = self.fonts;
+        let vertical = self.orientation == Orientation::Vertical;
+        let mut lines = Vec::new();
+        let mut rtl = Vec::new();
+        let mut index = 0;
+
+        for paragraph in text.split('\n') {
+            let (base, levels) = self.levels(paragraph);
+
+            let chars = self.items(paragraph, index, &levels);
+
+            let start = lines.len();
+            self.wrap(&chars, &mut lines);
+            reorder(&mut lines[start..], base);
+            rtl.resize(lines.len(), base % 2 == 1);
+
+            index += paragraph.chars().count() + 1;
+        }
+
+        let length = self.width.map_or_else(
+            || lines.iter().map(|(width, _)| *width).max().unwrap_or(0),
+            |width| width as i32,
+        );
+        let spacing = self.spacing() as i32;
+        let columns = lines.len() as i32;
+
+        lines
+            .into_iter()
+            .zip(rtl)
+            .enumerate()
+            .map(|(number, ((line_width, glyphs), rtl))| {
+                let start = match (self.align, rtl) {
+                    (Align::Left, _) | (Align::Start, false) | (Align::End, true) => 0,
+                    (Align::Center, _) => (length - line_width) / 2,
+                    _ => length - line_width,
+                };
+                let number = number as i32;
+
+                let (x, y) = if vertical {
+                    ((columns - number) * spacing - spacing / 2, start)
+                } else {
+                    (start, self.ascent() as i32 + number * spacing)
+                };
+
+                // The trailing spaces of right-to-left lines end up before the
+                // start of the line.
+                let mut pen = if rtl {
+                    line_width - glyphs.iter().map(|item| item.advance).sum::<i32>()
+                } else {
+                    0
+                };
+                let origins = glyphs
+                    .iter()
+                    .map(|item| {
+                        let offset = pen;
+                        pen += item.advance;
+
+                        if vertical {
+                            // Move from the vertical origin back to the
+                            // horizontal one.
+                            let (vx, vy) =
+                                fonts[item.font].vertical_origin(&self.glyph(item.codepoint).1);
+
+                            (x - vx, y + offset + vy)
+                        } else {
+                            (x + offset, y)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                Run {
+                    x,
+                    y,
+                    width: line_width.max(0) as u32,
+                    glyphs: glyphs
+                        .iter()
+                        .zip(&origins)
+                        .map(|(item, &origin)| {
+                            // Marks are placed over their base wherever it
+                            // ended up in the line.
+                            let (x, y) = item
+                                .mark
+                                .and_then(|(base, shift)| {
+                                    let position = glyphs.iter().position(|b| b.id == base)?;
+                                    let (x, y) = origins[position];
+
+                                    Some((x + shift, y))
+                                })
+                                .unwrap_or(origin);
+
+                            Positioned {
+                                index: item.index,
+                                codepoint: item.codepoint,
+                                font: item.font,
+                                x,
+                                y,
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the characters of a paragraph that have a glyph, decomposing the
+    /// ones that do not when possible.
+    ///
+    /// Combining marks following a base character do not advance, and are
+    /// offset from the origin of the base to the end of its advance when they
+    /// have no advance of their own, or centered over it when they do.
+    pub(crate) fn items(&self, paragraph: &str, index: usize, levels: &[u8]) -> Vec<Item> {
+        let fonts = self.fonts;
+        let mut items = Vec::new();
+        let mut base = None;
+
+        for (offset, c) in paragraph.chars().enumerate() {
+            if !is_drawn(c) {
+                continue;
+            }
+
+            let level = levels.get(offset).copied().unwrap_or(0);
+
+            for c in self.decompose(c) {
+                let (font, glyph) = self.glyph(c);
+                let width = self.width_of(font, &glyph, level);
+                let advance = match self.orientation {
+                    Orientation::Horizontal => width,
+
+                    Orientation::Vertical => fonts[font].vertical_advance(&glyph) as i32,
+                };
+
+                let id = items.len();
+                let mark = match base {
+                    // Fallbacks for missing marks are not combined.
+                    Some((base, base_width)) if is_mark(c) && self.contains(c) => {
+                        let shift = if width == 0 {
+                            base_width
+                        } else {
+                            (base_width - width) / 2
+                        };
+
+                        Some((base, shift))
+                    }
+
+                    _ => {
+                        base = Some((id, width));
+                        None
+                    }
+                };
+
+                items.push(Item {
+                    id,
+                    index: index + offset,
+                    codepoint: c,
+                    font,
+                    advance: if mark.is_some() { 0 } else { advance },
+                    level,
+                    mark,
+                });
+            }
+        }
+
+        items
+    }
+
+    /// Gets the characters to lay out for a character, its canonical
+    /// decomposition when no font has a glyph for it but there are glyphs for
+    /// every part.
+    #[cfg(feature = "normalization")]
+    fn decompose(&self, c: char) -> impl Iterator<Item = char> {
+        let mut chars = Vec::new();
+
+        if !self.contains(c) {
+            unicode_normalization::char::decompose_canonical(c, |c| chars.push(c));
+        }
+
+        if chars.is_empty() || !chars.iter().all(|&c| self.contains(c)) {
+            chars.clear();
+            chars.push(c);
+        }
+
+        chars.into_iter()
+    }
+
+    /// Gets the characters to lay out for a character, without decomposition.
+    #[cfg(not(feature = "normalization"))]
+    fn decompose(&self, c: char) -> impl Iterator<Item = char> {
+        core::iter::once(c)
+    }
+
+    /// Gets the horizontal advance of a glyph at the given embedding level,
+    /// the alternate advance for right-to-left levels.
+    pub(crate) fn width_of(&self, font: usize, glyph: &Glyph, level: u8) -> i32 {
+        let font = &self.fonts[font];
+
+        if level % 2 == 1 {
+            font.alternate_advance(glyph) as i32
+        } else {
+            font.advance(glyph) as i32
+        }
+    }
+
+    /// Resolves the paragraph level and the embedding level of every
+    /// character.
+    #[cfg(feature = "bidi")]
+    pub(crate) fn levels(&self, paragraph: &str) -> (u8, Vec<u8>) {
+        match self.orientation {
+            Orientation::Horizontal => crate::bidi::levels(paragraph),
+
+            Orientation::Vertical => (0, Vec::new()),
+        }
+    }
+
+    /// Resolves the paragraph level and the embedding level of every
+    /// character, always left-to-right without the `bidi` feature.
+    #[cfg(not(feature = "bidi"))]
+    pub(crate) fn levels(&self, _paragraph: &str) -> (u8, Vec<u8>) {
+        (0, Vec::new())
+    }
+
+    /// Breaks a paragraph into lines, made of their width without trailing
+    /// spaces and their characters.
+    fn wrap(&self, chars: &[Item], lines: &mut Vec<(i32, Vec<Item>)>) {
+        let limit = self.width.map_or(i32::MAX, |width| width as i32);
+        let mut line = Vec::new();
+        let mut pen = 0;
+        let mut width = 0;
+        let mut wrapped = false;
+
+        let mut rest = chars;
+        while !rest.is_empty() {
+            // Split off the spaces and the following word.
+            let spaces = rest.iter().take_while(|c| c.codepoint == ' ').count();
+            let word = rest[spaces..]
+                .iter()
+                .take_while(|c| c.codepoint != ' ')
+                .count();
+            let (segment, next) = rest.split_at(spaces + word);
+            rest = next;
+
+            let spaces_width = segment[..spaces].iter().map(|c| c.advance).sum::<i32>();
+            let word_width = segment[spaces..].iter().map(|c| c.advance).sum::<i32>();
+
+            if !line.is_empty() && word > 0 && pen + spaces_width + word_width > limit {
+                lines.push((width, line));
+                line = Vec::new();
+                pen = 0;
+                width = 0;
+                wrapped = true;
+            }
+
+            // Spaces where a line was wrapped are dropped.
+            let segment = if wrapped && line.is_empty() {
+                &segment[spaces..]
+            } else {
+                segment
+            };
+
+            for &item in segment {
+                // Break words that do not fit on their own line.
+                if item.codepoint != ' ' && !line.is_empty() && pen + item.advance > limit {
+                    lines.push((width, line));
+                    line = Vec::new();
+                    pen = 0;
+                    wrapped = true;
+                }
+
+                line.push(item);
+                pen += item.advance;
+
+                if item.codepoint != ' ' {
+                    width = pen;
+                }
+            }
+        }
+
+        lines.push((width, line));
+    }
+}
+
+/// Reorders the lines of a paragraph from logical to visual order.
+#[cfg(feature = "bidi")]
+fn reorder(lines: &mut [(i32, Vec<Item>)], base: u8) {
+    for (_, line) in lines {
+        crate::bidi::reorder(line, base, |c| (c.codepoint, c.level));
+    }
+}
+
+/// Keeps the lines in logical order without the `bidi` feature.
+#[cfg(not(feature = "bidi"))]
+fn reorder(_lines: &mut [(i32, Vec<Item>)], _base: u8) {}
+
+/// Checks if the character gets a glyph, control characters like tabs and
+/// carriage returns are skipped.
+#[inline]
+pub(crate) fn is_drawn(c: char) -> bool {
+    !c.is_control()
+}
+
+/// Checks if the character is a combining mark, from the combining diacritical
+/// blocks and the Hebrew and Arabic points.
+fn is_mark(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{0591}'..='\u{05BD}'
+        | '\u{05BF}'
+        | '\u{05C1}'..='\u{05C2}'
+        | '\u{05C4}'..='\u{05C5}'
+        | '\u{05C7}'
+        | '\u{0610}'..='\u{061A}'
+        | '\u{064B}'..='\u{065F}'
+        | '\u{0670}'
+        | '\u{06D6}'..='\u{06DC}'
+        | '\u{06DF}'..='\u{06E4}'
+        | '\u{06E7}'..='\u{06E8}'
+        | '\u{06EA}'..='\u{06ED}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE20}'..='\u{FE2F}')
+}
+
+/// A character of a paragraph with a glyph.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Item {
+    /// The position in the paragraph.
+    id: usize,
+
+    /// The index of the character in the text.
+    index: usize,
+
+    /// The codepoint of the glyph.
+    codepoint: char,
+
+    /// The index of the font of the glyph.
+    font: usize,
+
+    /// The advance in the direction of the line.
+    pub(crate) advance: i32,
+
+    /// The embedding level.
+    #[cfg_attr(not(feature = "bidi"), allow(dead_code))]
+    level: u8,
+
+    /// The base of a combining mark and the offset from its origin.
+    mark: Option<(usize, i32)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Align, Bitmap, BoundingBox, Font, Glyph, Layout, Orientation};
+
+    fn font() -> Font {
+        let mut font = Font::new("test", None);
+        font.set_bounds(BoundingBox {
+            width: 2,
+            height: 2,
+            x: 0,
+            y: 0,
+        });
+
+        for c in "abcdefgh אב\u{300}\u{301}".chars() {
+            let mut glyph = Glyph::new(c.to_string(), c);
+            glyph.set_bounds(BoundingBox {
+                width: 2,
+                height: 2,
+                x: 0,
+                y: 0,
+            });
+            glyph.set_device_width(Some((2, 0)));
+            glyph.set_map(Bitmap::new(2, 2));
+
+            font.glyphs_mut().insert(c, glyph);
+        }
+
+        font
+    }
+
+    fn lines(layout: &Layout, text: &str) -> Vec<(i32, String)> {
+        layout
+            .runs(text)
+            .into_iter()
+            .map(|run| (run.x, run.glyphs.iter().map(|g| g.codepoint).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn newlines() {
+        let font = font();
+        let runs = Layout::new(&font).runs("ab\nc");
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].y, 4);
+        assert_eq!(runs[1].glyphs[0].index, 3);
+        assert_eq!(runs[0].glyphs[1].x, 2);
+    }
+
+    #[test]
+    fn wrap() {
+        let font = font();
+        let layout = Layout::new(&font).width(10);
+
+        assert_eq!(
+            lines(&layout, "ab cd ef"),
+            vec![(0, "ab cd".into()), (0, "ef".into())]
+        );
+        assert_eq!(
+            lines(&layout, "abcdefgh a"),
+            vec![(0, "abcde".into()), (0, "fgh a".into())]
+        );
+        assert_eq!(
+            lines(&layout, "ab    cd"),
+            vec![(0, "ab".into()), (0, "cd".into())]
+        );
+        assert_eq!(
+            lines(&layout, "  abcdefgh"),
+            vec![(0, "  abc".into()), (0, "defgh".into())]
+        );
+    }
+
+    #[test]
+    fn align() {
+        let font = font();
+        let layout = Layout::new(&font).width(10);
+
+        assert_eq!(
+            lines(&layout.align(Align::Center), "ab cd ef"),
+            vec![(0, "ab cd".into()), (3, "ef".into())]
+        );
+        assert_eq!(
+            lines(&layout.align(Align::Right), "ab cd ef"),
+            vec![(0, "ab cd".into()), (6, "ef".into())]
+        );
+    }
+
+    #[test]
+    fn vertical() {
+        let mut font = font();
+        let layout = Layout::new(&font).orientation(Orientation::Vertical);
+        let runs = layout.runs("ab\nc");
+
+        assert_eq!(layout.size(&runs), (4, 4));
+        assert_eq!((runs[0].x, runs[0].y), (3, 0));
+        assert_eq!((runs[0].glyphs[0].x, runs[0].glyphs[0].y), (2, 2));
+        assert_eq!((runs[0].glyphs[1].x, runs[0].glyphs[1].y), (2, 4));
+        assert_eq!((runs[1].glyphs[0].x, runs[1].glyphs[0].y), (0, 2));
+
+        let glyph = font.glyphs_mut().get_mut(&'a').unwrap();
+        glyph.set_alternate_device_width(Some((0, -3)));
+        glyph.set_vector(Some((1, 1)));
+
+        let runs = Layout::new(&font)
+            .orientation(Orientation::Vertical)
+            .runs("ab");
+
+        assert_eq!((runs[0].glyphs[0].x, runs[0].glyphs[0].y), (0, 1));
+        assert_eq!((runs[0].glyphs[1].x, runs[0].glyphs[1].y), (0, 5));
+    }
+
+    #[test]
+    fn vertical_without_bounds() {
+        let mut font = Font::new("test", None);
+
+        for (c, width) in [('a', 2), ('b', 3)] {
+            let mut glyph = Glyph::new(c.to_string(), c);
+            glyph.set_bounds(BoundingBox {
+                width,
+                height: 2,
+                x: 0,
+                y: 0,
+            });
+            glyph.set_alternate_device_width(Some((0, -2)));
+            glyph.set_map(Bitmap::new(width, 2));
+
+            font.glyphs_mut().insert(c, glyph);
+        }
+
+        let layout = Layout::new(&font).orientation(Orientation::Vertical);
+        let runs = layout.runs("a\nb");
+
+        assert_eq!(layout.size(&runs), (6, 2));
+        assert_eq!((runs[0].x, runs[1].x), (5, 2));
+    }
+
+    #[test]
+    fn marks() {
+        let mut font = font();
+        font.glyphs_mut()
+            .get_mut(&'\u{301}')
+            .unwrap()
+            .set_device_width(Some((0, 0)));
+
+        let positions = |text: &str| {
+            Layout::new(&font).runs(text)[0]
+                .glyphs
+                .iter()
+                .map(|g| (g.index, g.x))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions("a\u{301}b"), vec![(0, 0), (1, 2), (2, 2)]);
+        assert_eq!(
+            positions("a\u{300}\u{301}b"),
+            vec![(0, 0), (1, 0), (2, 2), (3, 2)]
+        );
+        assert_eq!(positions("\u{300}a"), vec![(0, 0), (1, 2)]);
+        assert_eq!(font.text_width("a\u{300}\u{301}b"), 4);
+
+        #[cfg(feature = "normalization")]
+        assert_eq!(positions("\u{e1}b"), vec![(0, 0), (0, 2), (1, 2)]);
+    }
+
+    #[cfg(feature = "bidi")]
+    #[test]
+    fn bidi() {
+        let mut font = font();
+        let layout = Layout::new(&font).width(10);
+
+        assert_eq!(
+            lines(&layout, "ab אב cd"),
+            vec![(0, "ab בא".into()), (0, "cd".into())]
+        );
+        assert_eq!(lines(&layout, "אב ab"), vec![(0, "ab בא".into())]);
+
+        let glyph = font.glyphs_mut().get_mut(&'א').unwrap();
+        glyph.set_direction(crate::Direction::Alternate);
+        glyph.set_alternate_device_width(Some((3, 0)));
+
+        let runs = Layout::new(&font).runs("אבא");
+
+        assert_eq!(runs[0].width, 8);
+        assert_eq!(
+            runs[0]
+                .glyphs
+                .iter()
+                .map(|g| (g.index, g.x))
+                .collect::<Vec<_>>(),
+            vec![(2, 0), (1, 3), (0, 5)]
+        );
+        assert_eq!(font.index_at("אבא", 2), Some(2));
+        assert_eq!(font.index_at("אבא", 3), Some(1));
+        assert_eq!(font.index_at("אבא", 7), Some(0));
+        assert_eq!(font.index_at("אבא", 8), None);
+    }
+
+    #[cfg(feature = "bidi")]
+    #[test]
+    fn bidi_align() {
+        let font = font();
+        let layout = Layout::new(&font).width(6);
+
+        assert_eq!(
+            lines(&layout, "אב \nבא"),
+            vec![(2, " בא".into()), (2, "אב".into())]
+        );
+        assert_eq!(
+            layout.runs("אב ")[0]
+                .glyphs
+                .iter()
+                .map(|g| g.x)
+                .collect::<Vec<_>>(),
+            vec![0, 2, 4]
+        );
+
+        assert_eq!(
+            lines(&layout.align(Align::End), "אב \nבא"),
+            vec![(0, " בא".into()), (0, "אב".into())]
+        );
+        assert_eq!(
+            lines(&layout.align(Align::Left), "אב \nבא"),
+            vec![(0, " בא".into()), (0, "אב".into())]
+        );
+        assert_eq!(
+            lines(&layout.align(Align::End), "ab\nאב"),
+            vec![(2, "ab".into()), (0, "בא".into())]
+        );
+    }
+}
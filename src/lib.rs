@@ -2,6 +2,10 @@
 //!
 //! This crate allows you to read and write BDF fonts in Rust.
 //!
+//! The crate builds with Rust 1.61. Without the default `std` feature it only
+//! needs `alloc`, fonts are read from memory with `read_slice` and the float
+//! functions come from `libm`, but Rust 1.81 is required for `core::error::Error`.
+//!
 //! # Example
 //!
 //! This example will draw a given glyph in your terminal using the given font.
@@ -34,8 +38,10 @@
 //! }
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
 extern crate bit_set;
 
 mod property;
@@ -51,19 +57,72 @@ mod direction;
 pub use self::direction::Direction;
 
 mod bitmap;
-pub use self::bitmap::Bitmap;
+pub use self::bitmap::{Bitmap, Blend, Spans};
+
+mod packing;
+pub use self::packing::{BitOrder, Packing, Scan};
+
+mod rle;
+pub use self::rle::{RleBitmap, RleGlyph, RleSpans};
+
+mod ascii;
+pub use self::ascii::Ascii;
+
+mod scaler;
+pub use self::scaler::Scaler;
+
+mod graymap;
+pub use self::graymap::{Graymap, SUBPIXELS};
 
 mod font;
 pub use self::font::*;
 
+mod fallback;
+
+mod stack;
+pub use self::stack::FontStack;
+
+mod measure;
+
+mod layout;
+pub use self::layout::{Align, Layout, Orientation, Positioned, Run};
+
+#[cfg(feature = "bidi")]
+mod bidi;
+
+mod render;
+pub use self::render::{Renderer, Target};
+
+#[cfg(feature = "embedded-graphics")]
+mod graphics;
+#[cfg(feature = "embedded-graphics")]
+pub use self::graphics::BdfTextStyle;
+
+mod embedded;
+pub use self::embedded::{StaticFont, StaticGlyph};
+
+mod codegen;
+pub use self::codegen::Generator;
+
+mod export;
+pub use self::export::{Exporter, Format};
+
 mod entry;
 pub use self::entry::Entry;
 
 mod error;
 pub use self::error::Error;
 
+mod xlfd;
+
+mod math;
+
 mod reader;
+#[cfg(feature = "std")]
 pub use self::reader::{open, read, Reader};
+pub use self::reader::{read_slice, SliceReader};
 
+#[cfg(feature = "std")]
 mod writer;
+#[cfg(feature = "std")]
 pub use self::writer::{save, write, Writer};
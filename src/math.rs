@@ -0,0 +1,50 @@
+//! Float functions missing from `core`, from `std` when available and from
+//! `libm` otherwise.
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn ceil(value: f32) -> f32 {
+    value.ceil()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn ceil(value: f32) -> f32 {
+    libm::ceilf(value)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn floor(value: f32) -> f32 {
+    value.floor()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn floor(value: f32) -> f32 {
+    libm::floorf(value)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn round(value: f32) -> f32 {
+    value.round()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn round(value: f32) -> f32 {
+    libm::roundf(value)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn tan(value: f32) -> f32 {
+    value.tan()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn tan(value: f32) -> f32 {
+    libm::tanf(value)
+}
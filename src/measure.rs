@@ -0,0 +1,153 @@
+use crate::{BoundingBox, Font, FontStack, Layout, Run};
+
+impl Layout<'_> {
+    /// Gets the area covered by the set bits of the laid out glyphs, relative
+    /// to the origin of the first line, with positive `y` going up.
+    pub(crate) fn ink(&self, runs: &[Run]) -> Option<BoundingBox> {
+        let ascent = self.ascent() as i32;
+
+        runs.iter()
+            .flat_map(|run| run.glyphs.iter())
+            .filter_map(|g| {
+                let glyph = self.glyph(g.codepoint).1.trim();
+                let bbx = glyph.bounds();
+
+                if bbx.width == 0 {
+                    return None;
+                }
+
+                Some(BoundingBox {
+                    x: g.x + bbx.x,
+                    y: bbx.y + ascent - g.y,
+                    ..*bbx
+                })
+            })
+            .fold(None, |area, bbx| {
+                Some(area.map_or(bbx, |area: BoundingBox| area.union(&bbx)))
+            })
+    }
+
+    /// Gets the advance width of the text, the widest line for multi-line
+    /// text.
+    pub(crate) fn text_width(&self, text: &str) -> u32 {
+        text.split('\n')
+            .map(|line| {
+                self.items(line, 0, &self.levels(line).1)
+                    .iter()
+                    .map(|item| item.advance.max(0) as u32)
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Gets the index of the character whose advance contains the given
+    /// horizontal offset from the origin of the single line text.
+    pub(crate) fn index_at(&self, text: &str, x: i32) -> Option<usize> {
+        let runs = self.runs(text);
+        let (_, levels) = self.levels(text.split('\n').next().unwrap_or_default());
+
+        runs.first()?
+            .glyphs
+            .iter()
+            .find(|g| {
+                let level = levels.get(g.index).copied().unwrap_or(0);
+                let advance = self.width_of(g.font, &self.glyph(g.codepoint).1, level);
+
+                x >= g.x && x < g.x + advance
+            })
+            .map(|g| g.index)
+    }
+}
+
+impl Font {
+    /// Gets the distance between the baselines of two lines, the sum of the
+    /// ascent and descent.
+    #[inline]
+    pub fn line_height(&self) -> u32 {
+        self.ascent() + self.descent()
+    }
+
+    /// Gets the number of lines in the text.
+    #[inline]
+    pub fn line_count(&self, text: &str) -> usize {
+        text.split('\n').count()
+    }
+
+    /// Gets the advance width of the text, the widest line for multi-line text.
+    #[inline]
+    pub fn text_width(&self, text: &str) -> u32 {
+        Layout::new(self).text_width(text)
+    }
+
+    /// Gets the height of the text, the line height for every line.
+    #[inline]
+    pub fn text_height(&self, text: &str) -> u32 {
+        self.line_count(text) as u32 * self.line_height()
+    }
+
+    /// Gets the area covered by the set bits of the rendered text, relative to
+    /// the origin of the first line, with positive `y` going up.
+    ///
+    /// Returns `None` if nothing would be drawn.
+    #[inline]
+    pub fn text_bounds(&self, text: &str) -> Option<BoundingBox> {
+        let layout = Layout::new(self);
+        layout.ink(&layout.runs(text))
+    }
+
+    /// Gets the index of the character whose advance contains the given
+    /// horizontal offset from the origin of the single line text.
+    ///
+    /// Returns `None` if the offset is outside of the text.
+    #[inline]
+    pub fn index_at(&self, text: &str, x: i32) -> Option<usize> {
+        Layout::new(self).index_at(text, x)
+    }
+}
+
+impl FontStack {
+    /// Gets the distance between the baselines of two lines, the sum of the
+    /// largest ascent and descent.
+    #[inline]
+    pub fn line_height(&self) -> u32 {
+        self.ascent() + self.descent()
+    }
+
+    /// Gets the number of lines in the text.
+    #[inline]
+    pub fn line_count(&self, text: &str) -> usize {
+        text.split('\n').count()
+    }
+
+    /// Gets the advance width of the text, the widest line for multi-line text.
+    #[inline]
+    pub fn text_width(&self, text: &str) -> u32 {
+        Layout::stack(self).text_width(text)
+    }
+
+    /// Gets the height of the text, the line height for every line.
+    #[inline]
+    pub fn text_height(&self, text: &str) -> u32 {
+        self.line_count(text) as u32 * self.line_height()
+    }
+
+    /// Gets the area covered by the set bits of the rendered text, relative to
+    /// the origin of the first line, with positive `y` going up.
+    ///
+    /// Returns `None` if nothing would be drawn.
+    #[inline]
+    pub fn text_bounds(&self, text: &str) -> Option<BoundingBox> {
+        let layout = Layout::stack(self);
+        layout.ink(&layout.runs(text))
+    }
+
+    /// Gets the index of the character whose advance contains the given
+    /// horizontal offset from the origin of the single line text.
+    ///
+    /// Returns `None` if the offset is outside of the text.
+    #[inline]
+    pub fn index_at(&self, text: &str, x: i32) -> Option<usize> {
+        Layout::stack(self).index_at(text, x)
+    }
+}
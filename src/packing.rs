@@ -0,0 +1,236 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Bitmap, Error};
+
+/// The order the bits of a bitmap are packed in.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Scan {
+    /// Row by row from the top, with the bits of a byte going right.
+    Rows,
+
+    /// Column by column from the left, with the bits of a byte going down.
+    Columns,
+
+    /// Page by page from the top, every page a row of bytes each holding a
+    /// column of 8 bits going down, as used by SSD1306 and ST7565 displays.
+    Pages,
+}
+
+/// The order of the bits in a byte.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BitOrder {
+    /// The first bit is the most significant.
+    MsbFirst,
+
+    /// The first bit is the least significant.
+    LsbFirst,
+}
+
+/// How the bits of a bitmap are packed into raw bytes.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Packing {
+    scan: Scan,
+    order: BitOrder,
+    padded: bool,
+}
+
+impl Default for Packing {
+    #[inline]
+    fn default() -> Self {
+        Packing::new(Scan::Rows)
+    }
+}
+
+impl Packing {
+    /// Creates a packing in the given order, with the most significant bit
+    /// first for rows and columns and the least significant bit first for
+    /// pages, padding every row or column to a whole byte.
+    #[inline]
+    pub fn new(scan: Scan) -> Self {
+        Packing {
+            scan,
+            order: if scan == Scan::Pages {
+                BitOrder::LsbFirst
+            } else {
+                BitOrder::MsbFirst
+            },
+            padded: true,
+        }
+    }
+
+    /// Sets the order of the bits in a byte.
+    #[inline]
+    pub fn order(mut self, value: BitOrder) -> Self {
+        self.order = value;
+        self
+    }
+
+    /// Sets whether every row or column starts on a new byte, pages always
+    /// do.
+    #[inline]
+    pub fn padded(mut self, value: bool) -> Self {
+        self.padded = value;
+        self
+    }
+
+    /// Gets the number of bytes a bitmap of the given size is packed in.
+    pub fn len(&self, width: u32, height: u32) -> usize {
+        match self.scan {
+            Scan::Rows => (self.stride(width) as usize * height as usize + 7) / 8,
+
+            Scan::Columns => (self.stride(height) as usize * width as usize + 7) / 8,
+
+            Scan::Pages => (height as usize + 7) / 8 * width as usize,
+        }
+    }
+
+    /// Gets the number of bits in a row or column of the given length.
+    fn stride(&self, length: u32) -> u32 {
+        if self.padded {
+            (length + 7) / 8 * 8
+        } else {
+            length
+        }
+    }
+
+    /// Gets the byte holding the bit at the given position and its mask.
+    fn locate(&self, width: u32, height: u32, x: u32, y: u32) -> (usize, u8) {
+        let bit = match self.scan {
+            Scan::Rows => y as usize * self.stride(width) as usize + x as usize,
+
+            Scan::Columns => x as usize * self.stride(height) as usize + y as usize,
+
+            Scan::Pages => ((y / 8) as usize * width as usize + x as usize) * 8 + y as usize % 8,
+        };
+
+        let mask = match self.order {
+            BitOrder::MsbFirst => 0x80 >> (bit % 8),
+            BitOrder::LsbFirst => 1 << (bit % 8),
+        };
+
+        (bit / 8, mask)
+    }
+}
+
+impl Bitmap {
+    /// Packs the bits into raw bytes.
+    pub fn pack(&self, packing: Packing) -> Vec<u8> {
+        let (width, height) = (self.width(), self.height());
+        let mut bytes = vec![0; packing.len(width, height)];
+
+        for y in 0..height {
+            for x in 0..width {
+                if self.get(x, y) {
+                    let (index, mask) = packing.locate(width, height, x, y);
+                    bytes[index] |= mask;
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Unpacks a bitmap of the given size from raw bytes.
+    ///
+    /// Fails with `Error::Truncated` when there are fewer bytes than the
+    /// packing needs.
+    pub fn unpack(bytes: &[u8], width: u32, height: u32, packing: Packing) -> Result<Self, Error> {
+        let expected = packing.len(width, height);
+
+        if bytes.len() < expected {
+            return Err(Error::Truncated {
+                expected,
+                length: bytes.len(),
+            });
+        }
+
+        let mut map = Bitmap::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (index, mask) = packing.locate(width, height, x, y);
+                map.set(x, y, bytes[index] & mask != 0);
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitOrder, Packing, Scan};
+    use crate::{Bitmap, Error};
+
+    fn bitmap() -> Bitmap {
+        "
+            #........#
+            .#........
+            ..#.......
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn rows() {
+        let packing = Packing::new(Scan::Rows);
+        let bytes = [0x80, 0x40, 0x40, 0x00, 0x20, 0x00];
+
+        assert_eq!(bitmap().pack(packing), bytes);
+        assert_eq!(Bitmap::unpack(&bytes, 10, 3, packing).unwrap(), bitmap());
+    }
+
+    #[test]
+    fn rows_unpadded_lsb() {
+        let packing = Packing::new(Scan::Rows)
+            .order(BitOrder::LsbFirst)
+            .padded(false);
+        let bytes = [0x01, 0x0A, 0x40, 0x00];
+
+        assert_eq!(bitmap().pack(packing), bytes);
+        assert_eq!(Bitmap::unpack(&bytes, 10, 3, packing).unwrap(), bitmap());
+    }
+
+    #[test]
+    fn columns() {
+        let packing = Packing::new(Scan::Columns);
+        let bytes = [0x80, 0x40, 0x20, 0, 0, 0, 0, 0, 0, 0x80];
+
+        assert_eq!(bitmap().pack(packing), bytes);
+        assert_eq!(Bitmap::unpack(&bytes, 10, 3, packing).unwrap(), bitmap());
+    }
+
+    #[test]
+    fn pages() {
+        let packing = Packing::new(Scan::Pages);
+        let bytes = [0x01, 0x02, 0x04, 0, 0, 0, 0, 0, 0, 0x01];
+
+        assert_eq!(bitmap().pack(packing), bytes);
+        assert_eq!(Bitmap::unpack(&bytes, 10, 3, packing).unwrap(), bitmap());
+    }
+
+    #[test]
+    fn pages_tall() {
+        let mut map = Bitmap::new(2, 10);
+        map.set(0, 0, true);
+        map.set(1, 9, true);
+
+        assert_eq!(
+            map.pack(Packing::new(Scan::Pages)),
+            [0x01, 0x00, 0x00, 0x02]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(matches!(
+            Bitmap::unpack(&[0; 5], 10, 3, Packing::default()),
+            Err(Error::Truncated {
+                expected: 6,
+                length: 5
+            })
+        ));
+    }
+}
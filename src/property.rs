@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 /// A `Font` property.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum Property {
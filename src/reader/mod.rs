@@ -1,27 +1,69 @@
+mod parser;
+
+#[cfg(feature = "std")]
 #[allow(clippy::module_inception)]
 mod reader;
+#[cfg(feature = "std")]
 pub use self::reader::Reader;
 
+mod slice;
+pub use self::slice::SliceReader;
+
+#[cfg(feature = "std")]
 use std::fs::File;
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "std")]
 use std::path::Path;
 
 use crate::{font, Entry, Error, Font, Glyph};
 
 /// Create a `Reader` from a `Read`.
+#[cfg(feature = "std")]
 pub fn new<T: Read>(stream: T) -> Reader<T> {
     Reader::from(stream)
 }
 
 /// Open a BDF file and read it into a `Font`.
+#[cfg(feature = "std")]
 pub fn open<T: AsRef<Path>>(path: T) -> Result<Font, Error> {
     read(File::open(path)?)
 }
 
 /// Read a BDF stream into a `Font`.
+#[cfg(feature = "std")]
 pub fn read<T: Read>(stream: T) -> Result<Font, Error> {
+    new(stream).font()
+}
+
+/// Read a BDF font already in memory into a `Font`.
+pub fn read_slice<T: AsRef<[u8]> + ?Sized>(bytes: &T) -> Result<Font, Error> {
+    SliceReader::from(bytes.as_ref()).font()
+}
+
+/// A reader of entries the font is built from.
+trait Source {
+    fn entry(&mut self) -> Result<Entry, Error>;
+}
+
+#[cfg(feature = "std")]
+impl<T: Read> Source for Reader<T> {
+    #[inline]
+    fn entry(&mut self) -> Result<Entry, Error> {
+        Reader::entry(self)
+    }
+}
+
+impl Source for SliceReader<'_> {
+    #[inline]
+    fn entry(&mut self) -> Result<Entry, Error> {
+        SliceReader::entry(self)
+    }
+}
+
+/// Builds a `Font` out of the entries of the reader.
+fn build<R: Source>(reader: &mut R) -> Result<Font, Error> {
     let mut font = Font::default();
-    let mut reader = new(stream);
 
     let mut in_font = false;
     let mut in_props = false;
@@ -119,6 +161,8 @@ pub fn read<T: Read>(stream: T) -> Result<Font, Error> {
                 match entry {
                     Entry::Encoding(codepoint) => glyph.set_codepoint(codepoint),
 
+                    Entry::Direction(direction) => glyph.set_direction(direction),
+
                     Entry::ScalableWidth(x, y) => glyph.set_scalable_width(Some((x, y))),
 
                     Entry::DeviceWidth(x, y) => glyph.set_device_width(Some((x, y))),
@@ -152,6 +196,8 @@ pub fn read<T: Read>(stream: T) -> Result<Font, Error> {
 
                 Entry::Size(pt, x, y) => font.set_size(font::Size { pt, x, y }),
 
+                Entry::Direction(direction) => font.set_direction(direction),
+
                 Entry::FontBoundingBox(bbx) => font.set_bounds(bbx),
 
                 Entry::ScalableWidth(x, y) => font.set_scalable_width(Some((x, y))),
@@ -187,10 +233,15 @@ pub fn read<T: Read>(stream: T) -> Result<Font, Error> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{reader, Bitmap, BoundingBox, Direction, Entry, Property};
+    #[cfg(feature = "std")]
+    use crate::reader;
+    use crate::{Bitmap, BoundingBox, Direction, Entry, Error, Property, SliceReader};
 
     pub fn assert(string: &str, entry: Entry) {
-        let input = reader::new(string.as_bytes()).last().unwrap();
+        let input = SliceReader::from(string).last().unwrap();
+
+        #[cfg(feature = "std")]
+        assert_eq!(reader::new(string.as_bytes()).last().unwrap(), input);
 
         assert_eq!(input, entry);
     }
@@ -303,6 +354,7 @@ mod tests {
     #[test]
     fn device_width() {
         assert("DWIDTH 6 0\n", Entry::DeviceWidth(6, 0));
+        assert("DWIDTH -6 0\n", Entry::DeviceWidth(-6, 0));
     }
 
     #[test]
@@ -313,11 +365,13 @@ mod tests {
     #[test]
     fn alternate_device_width() {
         assert("DWIDTH1 6 0\n", Entry::AlternateDeviceWidth(6, 0));
+        assert("DWIDTH1 0 -16\n", Entry::AlternateDeviceWidth(0, -16));
     }
 
     #[test]
     fn vector() {
         assert("VVECTOR 6 0\n", Entry::Vector(6, 0));
+        assert("VVECTOR -4 12\n", Entry::Vector(-4, 12));
     }
 
     #[test]
@@ -418,4 +472,52 @@ mod tests {
     fn unknown() {
         assert("HUE", Entry::Unknown("HUE".to_owned()));
     }
+
+    #[test]
+    fn crlf() {
+        let mut reader = SliceReader::from("COMMENT \"hue\"\r\n\r\nENDFONT\r\n");
+
+        assert_eq!(reader.entry().unwrap(), Entry::Comment("hue".to_owned()));
+        assert_eq!(reader.entry().unwrap(), Entry::EndFont);
+        assert_eq!(reader.line_number(), 3);
+        assert!(matches!(reader.entry(), Err(Error::End)));
+    }
+
+    #[test]
+    fn metrics_set() {
+        let font = crate::read_slice(
+            "STARTFONT 2.2\n\
+             FONT test\n\
+             SIZE 16 75 75\n\
+             FONTBOUNDINGBOX 2 2 0 0\n\
+             METRICSSET 2\n\
+             CHARS 1\n\
+             STARTCHAR A\n\
+             ENCODING 65\n\
+             METRICSSET 1\n\
+             SWIDTH1 0 1000\n\
+             DWIDTH1 0 2\n\
+             BBX 2 2 0 0\n\
+             BITMAP\n\
+             80\n\
+             40\n\
+             ENDCHAR\n\
+             ENDFONT\n",
+        )
+        .unwrap();
+
+        assert_eq!(font.direction(), Direction::Both);
+        assert_eq!(font.glyphs()[&'A'].direction(), Direction::Alternate);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut reader = SliceReader::from(&b"COMMENT\nFONT \xff\n"[..]);
+
+        assert_eq!(reader.entry().unwrap(), Entry::Comment("".to_owned()));
+        assert!(matches!(
+            reader.entry(),
+            Err(Error::Utf8 { line_number: 2 })
+        ));
+    }
 }
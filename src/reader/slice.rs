@@ -0,0 +1,84 @@
+use core::str;
+
+use super::parser::Parser;
+use crate::{Entry, Error, Font};
+
+/// The font reader for fonts already in memory, which only needs `alloc`.
+pub struct SliceReader<'a> {
+    parser: Parser,
+    stream: SliceLines<'a>,
+}
+
+impl<'a> From<&'a [u8]> for SliceReader<'a> {
+    fn from(bytes: &'a [u8]) -> SliceReader<'a> {
+        SliceReader {
+            parser: Parser::default(),
+            stream: SliceLines {
+                rest: bytes,
+                line_number: 0,
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a str> for SliceReader<'a> {
+    #[inline]
+    fn from(string: &'a str) -> SliceReader<'a> {
+        SliceReader::from(string.as_bytes())
+    }
+}
+
+impl SliceReader<'_> {
+    /// Gets the number of lines read so far, the line of the last entry.
+    #[inline]
+    pub fn line_number(&self) -> u32 {
+        self.parser.line_number()
+    }
+
+    /// Reads the remaining entries into a `Font`, on errors `line_number` is
+    /// the line the reader failed on.
+    #[inline]
+    pub fn font(&mut self) -> Result<Font, Error> {
+        super::build(self)
+    }
+
+    /// Get the next entry.
+    pub fn entry(&mut self) -> Result<Entry, Error> {
+        self.parser.entry(&mut self.stream)
+    }
+}
+
+impl Iterator for SliceReader<'_> {
+    type Item = Entry;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        self.entry().ok()
+    }
+}
+
+/// The lines of a byte slice, split like `BufRead::lines` does.
+struct SliceLines<'a> {
+    rest: &'a [u8],
+    line_number: u32,
+}
+
+impl<'a> Iterator for SliceLines<'a> {
+    type Item = Result<&'a str, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let end = self.rest.iter().position(|&b| b == b'\n');
+        let line = &self.rest[..end.unwrap_or(self.rest.len())];
+        self.rest = &self.rest[end.map_or(self.rest.len(), |n| n + 1)..];
+
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        self.line_number += 1;
+
+        Some(str::from_utf8(line).map_err(|_| Error::Utf8 {
+            line_number: self.line_number,
+        }))
+    }
+}
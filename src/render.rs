@@ -0,0 +1,143 @@
+use crate::{Align, Bitmap, BoundingBox, Font, FontStack, Glyph, Layout, Orientation};
+
+/// Something glyphs can be drawn on.
+pub trait Target {
+    /// Sets the pixel at the given position, with positive `y` going down.
+    fn draw(&mut self, x: i32, y: i32);
+
+    /// Sets the pixels of a horizontal span starting at the given position,
+    /// for targets that can fill spans faster than single pixels.
+    #[inline]
+    fn span(&mut self, x: i32, y: i32, width: u32) {
+        for offset in 0..width as i32 {
+            self.draw(x + offset, y);
+        }
+    }
+}
+
+impl Target for Bitmap {
+    /// Sets the bit at the given position, ignoring positions outside of the
+    /// bitmap.
+    #[inline]
+    fn draw(&mut self, x: i32, y: i32) {
+        if x >= 0 && y >= 0 && (x as u32) < self.width() && (y as u32) < self.height() {
+            self.set(x as u32, y as u32, true);
+        }
+    }
+}
+
+impl<F: FnMut(i32, i32)> Target for F {
+    #[inline]
+    fn draw(&mut self, x: i32, y: i32) {
+        self(x, y)
+    }
+}
+
+/// Renders text using the metrics of a font or a font stack.
+///
+/// Glyphs are placed on the baseline using their `BBX` offsets as positioned by
+/// a `Layout`, lines are separated by the line height, which defaults to the
+/// sum of the font ascent and descent.
+#[derive(Clone, Copy, Debug)]
+pub struct Renderer<'a> {
+    layout: Layout<'a>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Creates a renderer for the given font.
+    #[inline]
+    pub fn new(font: &'a Font) -> Self {
+        Renderer {
+            layout: Layout::new(font),
+        }
+    }
+
+    /// Creates a renderer for the given font stack.
+    #[inline]
+    pub fn stack(fonts: &'a FontStack) -> Self {
+        Renderer {
+            layout: Layout::stack(fonts),
+        }
+    }
+
+    /// Sets the distance between the baselines of two lines.
+    #[inline]
+    pub fn line_height(mut self, value: u32) -> Self {
+        self.layout = self.layout.line_height(value);
+        self
+    }
+
+    /// Sets the width lines are wrapped at.
+    #[inline]
+    pub fn width(mut self, value: u32) -> Self {
+        self.layout = self.layout.width(value);
+        self
+    }
+
+    /// Sets the horizontal alignment of the lines.
+    #[inline]
+    pub fn align(mut self, value: Align) -> Self {
+        self.layout = self.layout.align(value);
+        self
+    }
+
+    /// Sets the direction lines are written in.
+    #[inline]
+    pub fn orientation(mut self, value: Orientation) -> Self {
+        self.layout = self.layout.orientation(value);
+        self
+    }
+
+    /// Draws the text on the target, with the top left corner of the text box
+    /// at the given position.
+    pub fn draw<T: Target>(&self, text: &str, x: i32, y: i32, target: &mut T) {
+        for run in self.layout.runs(text) {
+            for g in run.glyphs {
+                draw(&self.layout.glyph(g.codepoint).1, x + g.x, y + g.y, target);
+            }
+        }
+    }
+
+    /// Renders the text on a bitmap big enough for the text box and every
+    /// glyph.
+    pub fn render(&self, text: &str) -> Bitmap {
+        let runs = self.layout.runs(text);
+        let ascent = self.layout.ascent() as i32;
+        let (width, height) = self.layout.size(&runs);
+
+        // Both areas are relative to the first origin with positive `y` going
+        // up, so `y` is the bottom.
+        let mut area = BoundingBox {
+            width,
+            height,
+
+            x: 0,
+            y: ascent - height as i32,
+        };
+
+        if let Some(ink) = self.layout.ink(&runs) {
+            area = area.union(&ink);
+        }
+
+        let mut map = Bitmap::new(area.width, area.height);
+        self.draw(
+            text,
+            -area.x,
+            area.y + area.height as i32 - ascent,
+            &mut map,
+        );
+
+        map
+    }
+}
+
+/// Draws the glyph with the origin at the given position.
+pub(crate) fn draw<T: Target>(glyph: &Glyph, x: i32, y: i32, target: &mut T) {
+    let bbx = glyph.bounds();
+    let left = x + bbx.x;
+    let top = y - bbx.y - bbx.height as i32;
+
+    for (py, start, end) in glyph.map().spans() {
+        target.span(left + start as i32, top + py as i32, end - start);
+    }
+}
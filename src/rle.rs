@@ -0,0 +1,297 @@
+use alloc::vec::Vec;
+
+use crate::{Bitmap, BoundingBox, Font, Glyph, Target};
+
+/// A bitmap compressed into runs of bits, for fonts on devices short on
+/// memory.
+///
+/// The runs are bytes alternating between the lengths of runs of unset and
+/// set bits, starting with unset bits and going row by row from the top, so
+/// runs continue on the next row. Runs longer than 255 bits are split by
+/// empty runs of the other value, and the last run of unset bits is left out.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RleBitmap<T = Vec<u8>> {
+    width: u32,
+    height: u32,
+    runs: T,
+}
+
+impl RleBitmap {
+    /// Compresses the bitmap.
+    pub fn new(map: &Bitmap) -> Self {
+        let width = map.width();
+        let mut runs = Vec::new();
+        let mut end = 0;
+
+        for (y, start, stop) in map.spans() {
+            let start = y * width + start;
+
+            push(&mut runs, start - end, false);
+            push(&mut runs, y * width + stop - start, true);
+
+            end = y * width + stop;
+        }
+
+        RleBitmap {
+            width,
+            height: map.height(),
+            runs,
+        }
+    }
+}
+
+/// Appends a run, splitting it when longer than a byte and extending the
+/// last run when it has the same value, like spans continuing on the next
+/// row.
+fn push(runs: &mut Vec<u8>, mut length: u32, set: bool) {
+    // Runs of set bits are at odd indices.
+    if !runs.is_empty() && runs.len() % 2 != set as usize {
+        let last = runs.last_mut().unwrap();
+        let extra = length.min(255 - *last as u32);
+
+        *last += extra as u8;
+        length -= extra;
+    }
+
+    while length > 0 {
+        if runs.len() % 2 != set as usize {
+            runs.push(0);
+        }
+
+        runs.push(length.min(255) as u8);
+        length -= length.min(255);
+    }
+}
+
+impl<T: AsRef<[u8]>> RleBitmap<T> {
+    /// Creates a bitmap from already compressed runs, like static data.
+    #[inline]
+    pub const fn from_runs(width: u32, height: u32, runs: T) -> Self {
+        RleBitmap {
+            width,
+            height,
+            runs,
+        }
+    }
+
+    /// Gets the width.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the runs.
+    #[inline]
+    pub fn runs(&self) -> &[u8] {
+        self.runs.as_ref()
+    }
+
+    /// Creates an iterator over the horizontal runs of set bits, yielding
+    /// `(y, x_start, x_end)` like `Bitmap::spans`.
+    #[inline]
+    pub fn spans(&self) -> RleSpans<'_> {
+        RleSpans {
+            runs: self.runs(),
+            width: self.width,
+            height: self.height,
+            index: 0,
+            position: 0,
+            end: 0,
+        }
+    }
+
+    /// Decompresses the bitmap.
+    pub fn decode(&self) -> Bitmap {
+        let mut map = Bitmap::new(self.width, self.height);
+
+        for (y, start, end) in self.spans() {
+            for x in start..end {
+                map.set(x, y, true);
+            }
+        }
+
+        map
+    }
+
+    /// Draws the bitmap with the top left corner at the given position.
+    pub fn draw<U: Target>(&self, x: i32, y: i32, target: &mut U) {
+        for (py, start, end) in self.spans() {
+            target.span(x + start as i32, y + py as i32, end - start);
+        }
+    }
+}
+
+impl From<&Bitmap> for RleBitmap {
+    #[inline]
+    fn from(map: &Bitmap) -> Self {
+        RleBitmap::new(map)
+    }
+}
+
+/// An iterator over the horizontal runs of set bits of a compressed bitmap.
+#[derive(Clone, Debug)]
+pub struct RleSpans<'a> {
+    runs: &'a [u8],
+    width: u32,
+    height: u32,
+    index: usize,
+
+    /// The remaining bits of the current run of set bits.
+    position: u32,
+    end: u32,
+}
+
+impl Iterator for RleSpans<'_> {
+    type Item = (u32, u32, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.width == 0 {
+            return None;
+        }
+
+        while self.position >= self.end {
+            let unset = *self.runs.get(self.index)? as u32;
+            let set = self.runs.get(self.index + 1).copied().unwrap_or(0) as u32;
+
+            self.index += 2;
+            self.position = self.end + unset;
+            self.end = self.position + set;
+
+            // Runs split by empty runs of unset bits are merged back.
+            while self.runs.get(self.index) == Some(&0) {
+                self.end += self.runs.get(self.index + 1).copied().unwrap_or(0) as u32;
+                self.index += 2;
+            }
+        }
+
+        let y = self.position / self.width;
+        let row = y * self.width;
+
+        // Runs past the end of the bitmap are ignored.
+        if y >= self.height {
+            return None;
+        }
+
+        let start = self.position - row;
+
+        self.position = self.end.min(row + self.width);
+
+        Some((y, start, self.position - row))
+    }
+}
+
+/// A glyph with a compressed bitmap.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RleGlyph<T = Vec<u8>> {
+    /// The codepoint.
+    pub codepoint: char,
+
+    /// The bounds of the bitmap, with positive `y` going up.
+    pub bounds: BoundingBox,
+
+    /// The horizontal advance.
+    pub advance: u32,
+
+    /// The compressed bitmap.
+    pub map: RleBitmap<T>,
+}
+
+impl RleGlyph {
+    /// Compresses the glyph, with the advance from the font.
+    pub fn new(font: &Font, glyph: &Glyph) -> Self {
+        RleGlyph {
+            codepoint: glyph.codepoint(),
+            bounds: *glyph.bounds(),
+            advance: font.advance(glyph),
+            map: RleBitmap::new(glyph.map()),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> RleGlyph<T> {
+    /// Draws the glyph with the origin at the given position.
+    #[inline]
+    pub fn draw<U: Target>(&self, x: i32, y: i32, target: &mut U) {
+        self.map.draw(
+            x + self.bounds.x,
+            y - self.bounds.y - self.bounds.height as i32,
+            target,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RleBitmap, RleGlyph};
+    use crate::{Bitmap, BoundingBox, Font, Glyph};
+
+    #[test]
+    fn encode() {
+        let map: Bitmap = "
+            ..##
+            ##..
+            ....
+            ...#
+        "
+        .parse()
+        .unwrap();
+        let rle = RleBitmap::new(&map);
+
+        assert_eq!(rle.runs(), &[2, 4, 9, 1]);
+        assert_eq!(
+            rle.spans().collect::<Vec<_>>(),
+            map.spans().collect::<Vec<_>>()
+        );
+        assert_eq!(rle.decode(), map);
+    }
+
+    #[test]
+    fn long_runs() {
+        let mut map = Bitmap::new(300, 2);
+
+        for x in 0..300 {
+            map.set(x, 1, true);
+        }
+
+        let rle = RleBitmap::new(&map);
+
+        assert_eq!(rle.runs(), &[255, 0, 45, 255, 0, 45]);
+        assert_eq!(rle.spans().collect::<Vec<_>>(), [(1, 0, 300)]);
+        assert_eq!(rle.decode(), map);
+    }
+
+    #[test]
+    fn static_runs() {
+        static RUNS: [u8; 2] = [1, 2];
+        let rle = RleBitmap::from_runs(2, 2, &RUNS[..]);
+
+        assert_eq!(rle.spans().collect::<Vec<_>>(), [(0, 1, 2), (1, 0, 1)]);
+    }
+
+    #[test]
+    fn draw_glyph() {
+        let mut font = Font::new("test", None);
+        let mut glyph = Glyph::new("a", 'a');
+        glyph.set_bounds(BoundingBox {
+            width: 2,
+            height: 2,
+            x: 1,
+            y: -1,
+        });
+        glyph.set_map("#.\n.#".parse().unwrap());
+        font.glyphs_mut().insert('a', glyph.clone());
+
+        let rle = RleGlyph::new(&font, &glyph);
+        let mut map = Bitmap::new(4, 4);
+        rle.draw(0, 2, &mut map);
+
+        assert_eq!(rle.advance, 2);
+        assert_eq!(map, "....\n.#..\n..#.\n....".parse().unwrap());
+    }
+}
@@ -0,0 +1,362 @@
+use crate::Bitmap;
+
+/// The algorithm used to enlarge bitmaps.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Scaler {
+    /// Nearest neighbour, every bit becomes a square of bits.
+    Nearest,
+
+    /// Scale2x, applied repeatedly for factors that are powers of two.
+    Scale2x,
+
+    /// Scale3x, applied repeatedly for factors that are powers of three.
+    Scale3x,
+
+    /// Eric's Pixel Expansion, applied repeatedly for factors that are powers of
+    /// two.
+    ///
+    /// On two-colour images it gives the same results as `Scale2x`.
+    Epx,
+
+    /// xBR-style corner filling, cutting staircases along 45 degree diagonals,
+    /// usable with any factor.
+    Xbr,
+}
+
+impl Default for Scaler {
+    #[inline]
+    fn default() -> Self {
+        Scaler::Nearest
+    }
+}
+
+impl Scaler {
+    /// Enlarges the bitmap by the given factor.
+    ///
+    /// Panics if the factor is not supported by the algorithm.
+    pub fn scale(&self, map: &Bitmap, factor: u32) -> Bitmap {
+        match *self {
+            Scaler::Nearest => map.scale(factor),
+
+            Scaler::Scale2x => repeat(map, factor, 2, scale2x),
+
+            Scaler::Scale3x => repeat(map, factor, 3, scale3x),
+
+            Scaler::Epx => repeat(map, factor, 2, epx),
+
+            Scaler::Xbr => xbr(map, factor),
+        }
+    }
+}
+
+/// Applies a fixed factor algorithm until the factor is reached.
+fn repeat(map: &Bitmap, factor: u32, base: u32, f: fn(&Bitmap) -> Bitmap) -> Bitmap {
+    let mut map = map.clone();
+    let mut current = 1;
+
+    while current < factor {
+        map = f(&map);
+        current *= base;
+    }
+
+    assert!(current == factor, "invalid scale factor");
+
+    map
+}
+
+/// Gets a bit, treating everything outside of the bitmap as unset.
+#[inline]
+fn at(map: &Bitmap, x: i64, y: i64) -> bool {
+    x >= 0
+        && y >= 0
+        && x < map.width() as i64
+        && y < map.height() as i64
+        && map.get(x as u32, y as u32)
+}
+
+/// The neighbourhood of a bit, named as in the Scale2x and Scale3x
+/// descriptions.
+///
+/// ```text
+/// A B C
+/// D E F
+/// G H I
+/// ```
+struct Neighbours {
+    a: bool,
+    b: bool,
+    c: bool,
+    d: bool,
+    e: bool,
+    f: bool,
+    g: bool,
+    h: bool,
+    i: bool,
+}
+
+impl Neighbours {
+    #[inline]
+    fn new(map: &Bitmap, x: u32, y: u32) -> Self {
+        let (x, y) = (x as i64, y as i64);
+
+        Neighbours {
+            a: at(map, x - 1, y - 1),
+            b: at(map, x, y - 1),
+            c: at(map, x + 1, y - 1),
+            d: at(map, x - 1, y),
+            e: at(map, x, y),
+            f: at(map, x + 1, y),
+            g: at(map, x - 1, y + 1),
+            h: at(map, x, y + 1),
+            i: at(map, x + 1, y + 1),
+        }
+    }
+
+    /// Whether the top left corner continues a diagonal edge.
+    #[inline]
+    fn top_left(&self) -> bool {
+        self.d == self.b && self.b != self.f && self.d != self.h
+    }
+
+    /// Whether the top right corner continues a diagonal edge.
+    #[inline]
+    fn top_right(&self) -> bool {
+        self.b == self.f && self.b != self.d && self.f != self.h
+    }
+
+    /// Whether the bottom left corner continues a diagonal edge.
+    #[inline]
+    fn bottom_left(&self) -> bool {
+        self.d == self.h && self.d != self.b && self.h != self.f
+    }
+
+    /// Whether the bottom right corner continues a diagonal edge.
+    #[inline]
+    fn bottom_right(&self) -> bool {
+        self.h == self.f && self.d != self.h && self.b != self.f
+    }
+}
+
+fn scale2x(map: &Bitmap) -> Bitmap {
+    let mut result = Bitmap::new(map.width() * 2, map.height() * 2);
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let n = Neighbours::new(map, x, y);
+            let (x, y) = (x * 2, y * 2);
+
+            result.set(x, y, if n.top_left() { n.d } else { n.e });
+            result.set(x + 1, y, if n.top_right() { n.f } else { n.e });
+            result.set(x, y + 1, if n.bottom_left() { n.d } else { n.e });
+            result.set(x + 1, y + 1, if n.bottom_right() { n.f } else { n.e });
+        }
+    }
+
+    result
+}
+
+fn scale3x(map: &Bitmap) -> Bitmap {
+    let mut result = Bitmap::new(map.width() * 3, map.height() * 3);
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let n = Neighbours::new(map, x, y);
+            let (x, y) = (x * 3, y * 3);
+
+            let bits = [
+                if n.top_left() { n.d } else { n.e },
+                if (n.top_left() && n.e != n.c) || (n.top_right() && n.e != n.a) {
+                    n.b
+                } else {
+                    n.e
+                },
+                if n.top_right() { n.f } else { n.e },
+                if (n.top_left() && n.e != n.g) || (n.bottom_left() && n.e != n.a) {
+                    n.d
+                } else {
+                    n.e
+                },
+                n.e,
+                if (n.top_right() && n.e != n.i) || (n.bottom_right() && n.e != n.c) {
+                    n.f
+                } else {
+                    n.e
+                },
+                if n.bottom_left() { n.d } else { n.e },
+                if (n.bottom_left() && n.e != n.i) || (n.bottom_right() && n.e != n.g) {
+                    n.h
+                } else {
+                    n.e
+                },
+                if n.bottom_right() { n.f } else { n.e },
+            ];
+
+            for (index, &bit) in bits.iter().enumerate() {
+                result.set(x + index as u32 % 3, y + index as u32 / 3, bit);
+            }
+        }
+    }
+
+    result
+}
+
+fn epx(map: &Bitmap) -> Bitmap {
+    let mut result = Bitmap::new(map.width() * 2, map.height() * 2);
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let n = Neighbours::new(map, x, y);
+            let (x, y) = (x * 2, y * 2);
+
+            // In the original description the neighbours are named clockwise
+            // starting from the top, and the corners are numbered row by row.
+            let (top, right, left, bottom) = (n.b, n.f, n.d, n.h);
+            let mut bits = [n.e; 4];
+
+            if left == top {
+                bits[0] = top;
+            }
+
+            if top == right {
+                bits[1] = right;
+            }
+
+            if bottom == left {
+                bits[2] = left;
+            }
+
+            if right == bottom {
+                bits[3] = bottom;
+            }
+
+            let set = [top, right, left, bottom].iter().filter(|&&b| b).count();
+            if set != 2 {
+                bits = [n.e; 4];
+            }
+
+            result.set(x, y, bits[0]);
+            result.set(x + 1, y, bits[1]);
+            result.set(x, y + 1, bits[2]);
+            result.set(x + 1, y + 1, bits[3]);
+        }
+    }
+
+    result
+}
+
+fn xbr(map: &Bitmap, factor: u32) -> Bitmap {
+    let mut result = map.scale(factor);
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let n = Neighbours::new(map, x, y);
+            let corners = [
+                (n.top_left(), n.d, false, false),
+                (n.top_right(), n.f, true, false),
+                (n.bottom_left(), n.d, false, true),
+                (n.bottom_right(), n.f, true, true),
+            ];
+
+            for &(edge, bit, right, bottom) in &corners {
+                if !edge {
+                    continue;
+                }
+
+                // Fill the triangle cut by the line going through the middle
+                // of the two sides meeting at the corner.
+                for j in 0..factor {
+                    for i in 0..factor {
+                        if (i + j + 1) * 2 > factor {
+                            continue;
+                        }
+
+                        let dx = if right { factor - i - 1 } else { i };
+                        let dy = if bottom { factor - j - 1 } else { j };
+
+                        result.set(x * factor + dx, y * factor + dy, bit);
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, Scaler};
+
+    fn map(width: u32, height: u32, bits: &[(u32, u32)]) -> Bitmap {
+        let mut map = Bitmap::new(width, height);
+
+        for &(x, y) in bits {
+            map.set(x, y, true);
+        }
+
+        map
+    }
+
+    #[test]
+    fn nearest() {
+        let input = map(2, 2, &[(0, 0), (1, 1)]);
+
+        assert_eq!(Scaler::Nearest.scale(&input, 3), input.scale(3));
+    }
+
+    #[test]
+    fn scale2x() {
+        let input = map(2, 2, &[(0, 0), (1, 1)]);
+
+        assert_eq!(
+            Scaler::Scale2x.scale(&input, 2),
+            map(
+                4,
+                4,
+                &[
+                    (0, 0),
+                    (1, 0),
+                    (0, 1),
+                    (1, 1),
+                    (2, 1),
+                    (1, 2),
+                    (2, 2),
+                    (3, 2),
+                    (2, 3),
+                    (3, 3)
+                ]
+            )
+        );
+        assert_eq!(
+            Scaler::Epx.scale(&input, 4),
+            Scaler::Scale2x.scale(&input, 4)
+        );
+    }
+
+    #[test]
+    fn scale3x() {
+        let input = map(2, 2, &[(0, 0), (1, 1)]);
+        let output = Scaler::Scale3x.scale(&input, 3);
+
+        assert_eq!(output.width(), 6);
+        assert!(output.get(3, 2) && output.get(2, 3));
+        assert!(!output.get(5, 0) && !output.get(0, 5));
+    }
+
+    #[test]
+    fn xbr() {
+        let input = map(2, 2, &[(0, 0), (1, 1)]);
+
+        assert_eq!(
+            Scaler::Xbr.scale(&input, 2),
+            Scaler::Scale2x.scale(&input, 2)
+        );
+        assert_eq!(Scaler::Xbr.scale(&input, 1), input);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_factor() {
+        Scaler::Scale2x.scale(&Bitmap::new(1, 1), 3);
+    }
+}
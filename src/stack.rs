@@ -0,0 +1,164 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Font, Glyph, Layout};
+
+/// A list of fonts, where every character uses the first font with a glyph
+/// for it.
+///
+/// Characters missing from every font use the fallback glyph of the first
+/// font.
+#[derive(Clone, Debug)]
+pub struct FontStack {
+    fonts: Vec<Font>,
+}
+
+impl FontStack {
+    /// Creates a stack with the given primary font.
+    #[inline]
+    pub fn new(font: Font) -> Self {
+        FontStack { fonts: vec![font] }
+    }
+
+    /// Adds a font used for the characters missing from the previous ones.
+    #[inline]
+    pub fn push(&mut self, font: Font) {
+        self.fonts.push(font);
+    }
+
+    /// Gets the fonts.
+    #[inline]
+    pub fn fonts(&self) -> &[Font] {
+        &self.fonts
+    }
+
+    /// Gets a mutable reference to the fonts.
+    #[inline]
+    pub fn fonts_mut(&mut self) -> &mut [Font] {
+        &mut self.fonts
+    }
+
+    /// Gets the largest distance from the baseline to the top of a line.
+    #[inline]
+    pub fn ascent(&self) -> u32 {
+        self.fonts.iter().map(Font::ascent).max().unwrap_or(0)
+    }
+
+    /// Gets the largest distance from the baseline to the bottom of a line.
+    #[inline]
+    pub fn descent(&self) -> u32 {
+        self.fonts.iter().map(Font::descent).max().unwrap_or(0)
+    }
+
+    /// Gets the first font with a glyph for the character.
+    #[inline]
+    pub fn font_for(&self, codepoint: char) -> Option<&Font> {
+        self.fonts
+            .iter()
+            .find(|font| font.glyphs().contains_key(&codepoint))
+    }
+
+    /// Gets the glyph for the character from the first font that has it, or
+    /// the fallback glyph of the first font.
+    #[inline]
+    pub fn glyph_or_fallback(&self, codepoint: char) -> Cow<'_, Glyph> {
+        Layout::stack(self).glyph(codepoint).1
+    }
+}
+
+impl From<Font> for FontStack {
+    #[inline]
+    fn from(font: Font) -> Self {
+        FontStack::new(font)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, BoundingBox, Font, FontStack, Glyph, Layout, Renderer};
+
+    fn font(codepoint: char, bounds: BoundingBox) -> Font {
+        let mut font = Font::new(codepoint.to_string(), None);
+        font.set_bounds(bounds);
+
+        let mut map = Bitmap::new(bounds.width, bounds.height);
+        map.fill(true);
+
+        let mut glyph = Glyph::new(codepoint.to_string(), codepoint);
+        glyph.set_bounds(bounds);
+        glyph.set_device_width(Some((bounds.width as i32, 0)));
+        glyph.set_map(map);
+        font.glyphs_mut().insert(codepoint, glyph);
+
+        font
+    }
+
+    fn stack() -> FontStack {
+        let mut stack = FontStack::new(font(
+            'a',
+            BoundingBox {
+                width: 2,
+                height: 2,
+                x: 0,
+                y: 0,
+            },
+        ));
+
+        stack.push(font(
+            'b',
+            BoundingBox {
+                width: 3,
+                height: 4,
+                x: 0,
+                y: -1,
+            },
+        ));
+
+        stack
+    }
+
+    #[test]
+    fn metrics() {
+        let stack = stack();
+
+        assert_eq!((stack.ascent(), stack.descent()), (3, 1));
+        assert_eq!(stack.line_height(), 4);
+        assert_eq!(stack.text_width("ab\nb"), 5);
+        assert_eq!(stack.index_at("ab", 3), Some(1));
+        assert_eq!(stack.font_for('b').map(|font| font.name()), Some("b"));
+        assert_eq!(stack.glyph_or_fallback('c').name(), "uni0063");
+    }
+
+    #[test]
+    fn layout() {
+        let stack = stack();
+        let runs = Layout::stack(&stack).runs("abc");
+
+        assert_eq!(
+            runs[0]
+                .glyphs
+                .iter()
+                .map(|g| (g.font, g.x, g.y))
+                .collect::<Vec<_>>(),
+            vec![(0, 0, 3), (1, 2, 3), (0, 5, 3)]
+        );
+    }
+
+    #[test]
+    fn render() {
+        let stack = stack();
+
+        assert_eq!(
+            Renderer::stack(&stack).render("ab"),
+            "
+                ..###
+                #####
+                #####
+                ..###
+            "
+            .parse()
+            .unwrap()
+        );
+    }
+}
@@ -255,6 +255,7 @@ mod tests {
     #[test]
     fn alternate_device_width() {
         assert(Entry::AlternateDeviceWidth(6, 0), "DWIDTH1 6 0\n");
+        assert(Entry::AlternateDeviceWidth(0, -16), "DWIDTH1 0 -16\n");
     }
 
     #[test]
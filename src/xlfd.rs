@@ -0,0 +1,49 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Field index of the weight name.
+pub const WEIGHT_NAME: usize = 3;
+
+/// Field index of the slant.
+pub const SLANT: usize = 4;
+
+/// Field index of the pixel size.
+pub const PIXEL_SIZE: usize = 7;
+
+/// Field index of the point size.
+pub const POINT_SIZE: usize = 8;
+
+/// Field index of the average width.
+pub const AVERAGE_WIDTH: usize = 12;
+
+/// The number of fields in a name.
+const FIELDS: usize = 14;
+
+/// Replaces a field of the name, if it is a valid XLFD name.
+pub fn set(name: &str, field: usize, value: &str) -> Option<String> {
+    let mut fields = name.split('-').collect::<Vec<_>>();
+
+    if fields.len() != FIELDS + 1 || !fields[0].is_empty() {
+        return None;
+    }
+
+    fields[field] = value;
+
+    Some(fields.join("-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME: &str = "-Gohu-GohuFont-Medium-R-Normal--11-80-100-100-C-60-ISO10646-1";
+
+    #[test]
+    fn set_field() {
+        assert_eq!(
+            set(NAME, WEIGHT_NAME, "Bold").as_deref(),
+            Some("-Gohu-GohuFont-Bold-R-Normal--11-80-100-100-C-60-ISO10646-1")
+        );
+        assert_eq!(set("gohufont", WEIGHT_NAME, "Bold"), None);
+    }
+}
@@ -0,0 +1,36 @@
+/// Generated from the `-Gohu-GohuFont-Medium-R-Normal--11-80-100-100-C-60-ISO10646-1` font.
+pub static GOHUFONT: bdf::StaticFont = bdf::StaticFont {
+    name: "-Gohu-GohuFont-Medium-R-Normal--11-80-100-100-C-60-ISO10646-1",
+    ascent: 9,
+    descent: 2,
+    default: None,
+    glyphs: &[
+        bdf::StaticGlyph {
+            codepoint: ',',
+            width: 2,
+            height: 4,
+            x: 1,
+            y: -2,
+            advance: 6,
+            bitmap: &[0xc0, 0xc0, 0x40, 0x80],
+        },
+        bdf::StaticGlyph {
+            codepoint: 'H',
+            width: 5,
+            height: 8,
+            x: 0,
+            y: 0,
+            advance: 6,
+            bitmap: &[0x88, 0x88, 0x88, 0xf8, 0x88, 0x88, 0x88, 0x88],
+        },
+        bdf::StaticGlyph {
+            codepoint: 'i',
+            width: 3,
+            height: 7,
+            x: 1,
+            y: 0,
+            advance: 6,
+            bitmap: &[0x40, 0x00, 0xc0, 0x40, 0x40, 0x40, 0x60],
+        },
+    ],
+};
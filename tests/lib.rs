@@ -41,3 +41,776 @@ fn open_zpix_font() {
 fn open_fail() {
     bdf::open("hue").unwrap();
 }
+
+#[test]
+fn read_slice_gohufont_font() {
+    let bytes = std::fs::read("tests/gohufont.bdf").unwrap();
+    let font = bdf::read_slice(&bytes).unwrap();
+
+    let expected = bdf::open("tests/gohufont.bdf").unwrap();
+
+    assert_eq!(font.name(), expected.name());
+    assert_eq!(font.properties(), expected.properties());
+    assert_eq!(font.glyphs().len(), expected.glyphs().len());
+
+    for (codepoint, glyph) in expected.glyphs() {
+        assert_eq!(font.glyphs()[codepoint].bounds(), glyph.bounds());
+        assert_eq!(font.glyphs()[codepoint].map(), glyph.map());
+    }
+}
+
+#[test]
+fn read_malformed_font() {
+    let mut reader = bdf::Reader::from("STARTFONT 2.1\nFONT test\n\nSTARTCHAR a\nFOO\n".as_bytes());
+
+    assert!(matches!(reader.font(), Err(bdf::Error::MalformedChar)));
+    assert_eq!(reader.line_number(), 5);
+}
+
+#[test]
+fn trim_and_pad_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let padded = font.pad();
+    let trimmed = padded.trim();
+
+    for (codepoint, glyph) in padded.glyphs() {
+        assert_eq!(glyph.bounds(), font.bounds());
+        assert_eq!(
+            trimmed.glyphs()[codepoint].map(),
+            font.glyphs()[codepoint].trim().map()
+        );
+        assert_eq!(
+            trimmed.glyphs()[codepoint].bounds(),
+            font.glyphs()[codepoint].trim().bounds()
+        );
+    }
+}
+
+#[test]
+fn embolden_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let bold = font.embolden(1);
+
+    assert_eq!(
+        bold.name(),
+        "-Gohu-GohuFont-Bold-R-Normal--11-80-100-100-C-70-ISO10646-1"
+    );
+    assert_eq!(
+        bold.properties()["WEIGHT_NAME"],
+        bdf::Property::String("Bold".into())
+    );
+    assert_eq!(bold.bounds().width, font.bounds().width + 1);
+
+    let glyph = &bold.glyphs()[&'A'];
+    assert_eq!(glyph.device_width(), Some(&(7, 0)));
+    assert_eq!(glyph.width(), font.glyphs()[&'A'].width() + 1);
+}
+
+#[test]
+fn oblique_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let oblique = font.oblique(bdf::Slant::Step(4));
+
+    assert_eq!(
+        oblique.name(),
+        "-Gohu-GohuFont-Medium-O-Normal--11-80-100-100-C-60-ISO10646-1"
+    );
+    assert_eq!(
+        oblique.properties()["SLANT"],
+        bdf::Property::String("O".into())
+    );
+
+    for (codepoint, glyph) in oblique.glyphs() {
+        let bounds = glyph.bounds();
+
+        assert_eq!(
+            bounds.union(oblique.bounds()),
+            *oblique.bounds(),
+            "{:?}",
+            codepoint
+        );
+        assert_eq!(
+            glyph.device_width(),
+            font.glyphs()[codepoint].device_width()
+        );
+    }
+}
+
+#[test]
+fn reverse_oblique_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let oblique = font.oblique(bdf::Slant::Angle(-14.0));
+
+    assert_eq!(bdf::Slant::Angle(-14.0).step(), Some(-4));
+    assert_eq!(
+        oblique.properties()["SLANT"],
+        bdf::Property::String("RO".into())
+    );
+
+    for (codepoint, glyph) in oblique.glyphs() {
+        assert_eq!(
+            glyph.bounds().union(oblique.bounds()),
+            *oblique.bounds(),
+            "{:?}",
+            codepoint
+        );
+        assert_eq!(
+            glyph.flip_horizontal().map(),
+            font.glyphs()[codepoint].flip_horizontal().shear(4).map(),
+            "{:?}",
+            codepoint
+        );
+    }
+}
+
+#[test]
+fn upright_oblique_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+
+    for slant in [bdf::Slant::Angle(0.0), bdf::Slant::Step(0)] {
+        let oblique = font.oblique(slant);
+
+        assert_eq!(oblique.name(), font.name());
+        assert_eq!(oblique.bounds(), font.bounds());
+        assert_eq!(oblique.properties(), font.properties());
+    }
+
+    assert_eq!(bdf::Slant::Angle(0.001).step(), Some(57296));
+}
+
+#[test]
+fn scale_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let scaled = font.scale(2);
+
+    assert_eq!(
+        scaled.name(),
+        "-Gohu-GohuFont-Medium-R-Normal--22-160-100-100-C-120-ISO10646-1"
+    );
+    assert_eq!(scaled.size().pt, 22);
+    assert_eq!(
+        *scaled.bounds(),
+        bdf::BoundingBox {
+            width: 12,
+            height: 22,
+            x: 0,
+            y: -4,
+        }
+    );
+    assert_eq!(
+        scaled.properties()["FONT_ASCENT"],
+        bdf::Property::Integer(18)
+    );
+    assert_eq!(
+        scaled.properties()["FONT_DESCENT"],
+        bdf::Property::Integer(4)
+    );
+
+    let glyph = &scaled.glyphs()[&'A'];
+    assert_eq!(glyph.device_width(), Some(&(12, 0)));
+    assert_eq!(glyph.height(), font.glyphs()[&'A'].height() * 2);
+
+    let mut output = Vec::new();
+    bdf::write(&mut output, &scaled).unwrap();
+    assert_eq!(
+        bdf::read(&output[..]).unwrap().glyphs().len(),
+        font.glyphs().len()
+    );
+}
+
+#[test]
+fn scale_gohufont_font_with_scalers() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+
+    for &scaler in &[bdf::Scaler::Scale2x, bdf::Scaler::Epx, bdf::Scaler::Xbr] {
+        let scaled = font.scale_with(4, scaler);
+
+        assert_eq!(scaled.bounds(), font.scale(4).bounds());
+        assert_eq!(
+            scaled.glyphs()[&'/'].height(),
+            font.glyphs()[&'/'].height() * 4
+        );
+        assert_ne!(
+            scaled.glyphs()[&'/'].map(),
+            font.scale(4).glyphs()[&'/'].map()
+        );
+    }
+}
+
+#[test]
+fn coverage_gohufont_glyph() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let glyph = &font.glyphs()[&'g'];
+
+    let (map, bounds) = glyph.coverage(1.0, 0, 0).unwrap();
+    assert_eq!(bounds, *glyph.bounds());
+    assert_eq!((map.width(), map.height()), (glyph.width(), glyph.height()));
+
+    let (_, bounds) = glyph
+        .coverage(0.5, bdf::SUBPIXELS / 2, bdf::SUBPIXELS / 2)
+        .unwrap();
+    assert!(bounds.y < 0);
+    assert!(bounds.width <= glyph.width() / 2 + 1);
+}
+
+#[test]
+fn pack_gohufont_font() {
+    use bdf::{BitOrder, Bitmap, Packing, Scan};
+
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let packings = [
+        Packing::new(Scan::Rows),
+        Packing::new(Scan::Rows).padded(false),
+        Packing::new(Scan::Columns).order(BitOrder::LsbFirst),
+        Packing::new(Scan::Pages),
+    ];
+
+    for glyph in font.glyphs().values() {
+        for &packing in &packings {
+            let bytes = glyph.map().pack(packing);
+
+            assert_eq!(bytes.len(), packing.len(glyph.width(), glyph.height()));
+            assert_eq!(
+                &Bitmap::unpack(&bytes, glyph.width(), glyph.height(), packing).unwrap(),
+                glyph.map()
+            );
+        }
+    }
+
+    // The bar is a single column of 9 set bits, split over two pages.
+    let bytes = font.glyphs()[&'|'].map().pack(Packing::new(Scan::Pages));
+    assert_eq!(bytes, [0xFF, 0x01]);
+}
+
+#[test]
+fn rle_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+
+    for glyph in font.glyphs().values() {
+        let rle = bdf::RleGlyph::new(&font, glyph);
+
+        assert_eq!(
+            rle.map.spans().collect::<Vec<_>>(),
+            glyph.map().spans().collect::<Vec<_>>()
+        );
+        assert_eq!(&rle.map.decode(), glyph.map());
+
+        // Layouts skip control characters.
+        if glyph.codepoint().is_control() {
+            continue;
+        }
+
+        // The renderer takes the top of the line and the glyph its origin,
+        // on the baseline an ascent below.
+        let (x, top) = (5, 6);
+        let mut drawn = bdf::Bitmap::new(20, 20);
+        let mut rendered = bdf::Bitmap::new(20, 20);
+        rle.draw(x, top + font.ascent() as i32, &mut drawn);
+        bdf::Renderer::new(&font).draw(&glyph.codepoint().to_string(), x, top, &mut rendered);
+
+        assert_eq!(drawn, rendered);
+    }
+}
+
+#[test]
+fn outline_gohufont_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let outline = font.outline(1);
+
+    assert_eq!(
+        *outline.bounds(),
+        bdf::BoundingBox {
+            width: 8,
+            height: 13,
+            x: -1,
+            y: -3,
+        }
+    );
+
+    for (codepoint, glyph) in outline.glyphs() {
+        let original = &font.glyphs()[codepoint];
+
+        assert_eq!(glyph.device_width(), original.device_width());
+        assert_eq!(glyph.bounds().x, original.bounds().x - 1);
+
+        for ((x, y), value) in original.pixels() {
+            if value {
+                assert!(!glyph.get(x + 1, y + 1));
+            }
+        }
+    }
+}
+
+#[test]
+fn render_gohufont_text() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let map = bdf::Renderer::new(&font).render("Hi,\ng");
+
+    assert_eq!(
+        map,
+        "
+        ..................
+        #...#.............
+        #...#...#.........
+        #...#.............
+        #####..##.........
+        #...#...#.........
+        #...#...#.........
+        #...#...#....##...
+        #...#...##...##...
+        ..............#...
+        .............#....
+        ..................
+        ..................
+        ..................
+        ..................
+        .####.............
+        #...#.............
+        #...#.............
+        #..##.............
+        .##.#.............
+        ....#.............
+        .###..............
+        "
+        .parse()
+        .unwrap()
+    );
+
+    let mut pixels = Vec::new();
+    bdf::Renderer::new(&font)
+        .line_height(20)
+        .draw("i\ni", 10, 5, &mut |x, y| pixels.push((x, y)));
+
+    assert!(pixels.contains(&(12, 7)));
+    assert!(pixels.contains(&(12, 27)));
+}
+
+#[test]
+fn measure_gohufont_text() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+
+    assert_eq!(font.line_height(), 11);
+    assert_eq!(font.line_count("Hi,\ng"), 2);
+    assert_eq!(font.text_width("Hi,\ng"), 18);
+    assert_eq!(font.text_height("Hi,\ng"), 22);
+    assert_eq!(font.text_width(""), 0);
+    assert_eq!(font.text_width("e\u{301}"), 6);
+
+    assert_eq!(
+        font.text_bounds("Hi,\ng"),
+        Some(bdf::BoundingBox {
+            width: 15,
+            height: 21,
+            x: 0,
+            y: -13,
+        })
+    );
+    assert_eq!(font.text_bounds(" "), None);
+
+    assert_eq!(font.index_at("Hi,", -1), None);
+    assert_eq!(font.index_at("Hi,", 0), Some(0));
+    assert_eq!(font.index_at("Hi,", 7), Some(1));
+    assert_eq!(font.index_at("Hi,", 17), Some(2));
+    assert_eq!(font.index_at("Hi,", 18), None);
+}
+
+#[test]
+fn fallback_gohufont_glyph() {
+    let mut font = bdf::open("tests/gohufont.bdf").unwrap();
+    let glyph = font.glyph_or_fallback('\u{10FFFF}');
+
+    assert_eq!(glyph.name(), "u10FFFF");
+    assert_eq!(glyph.bounds(), font.bounds());
+    assert_eq!(glyph.map().ink_bounds(), Some((0, 0, 6, 11)));
+    assert_eq!(font.text_width("a\u{10FFFF}"), 12);
+
+    font.properties_mut()
+        .insert("DEFAULT_CHAR".into(), bdf::Property::Integer(0xFFFD));
+
+    assert_eq!(
+        font.glyph_or_fallback('\u{10FFFF}').map(),
+        font.glyphs()[&'\u{FFFD}'].map()
+    );
+}
+
+#[test]
+fn layout_gohufont_text() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let runs = bdf::Layout::new(&font)
+        .width(30)
+        .align(bdf::Align::Center)
+        .runs("one two three");
+
+    assert_eq!(runs.len(), 3);
+    assert_eq!((runs[0].x, runs[0].width, runs[0].y), (6, 18, 9));
+    assert_eq!((runs[2].x, runs[2].width, runs[2].y), (0, 30, 31));
+    assert_eq!(runs[2].glyphs[0].index, 8);
+
+    let map = bdf::Renderer::new(&font)
+        .width(30)
+        .align(bdf::Align::Center)
+        .render("one two three");
+
+    assert_eq!((map.width(), map.height()), (30, 33));
+}
+
+#[test]
+fn render_gohufont_vertical_text() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let renderer = bdf::Renderer::new(&font).orientation(bdf::Orientation::Vertical);
+    let map = renderer.render("Hi\nH");
+
+    assert_eq!((map.width(), map.height()), (12, 22));
+    assert_eq!(
+        map.crop(6, 0, 6, 22),
+        bdf::Renderer::new(&font).render("H\ni").crop(0, 0, 6, 22)
+    );
+}
+
+#[cfg(feature = "embedded-graphics")]
+#[test]
+fn draw_gohufont_embedded_graphics() {
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::prelude::*;
+    use embedded_graphics::text::renderer::CharacterStyle;
+    use embedded_graphics::text::{Baseline, Text};
+
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let mut style = bdf::BdfTextStyle::new(&font, BinaryColor::On);
+
+    let mut display = MockDisplay::new();
+    Text::new("Hi", Point::new(1, 8), style)
+        .draw(&mut display)
+        .unwrap();
+
+    display.assert_pattern(&[
+        "           ",
+        " #   #     ",
+        " #   #   # ",
+        " #   #     ",
+        " #####  ## ",
+        " #   #   # ",
+        " #   #   # ",
+        " #   #   # ",
+        " #   #   ##",
+    ]);
+
+    style.set_background_color(Some(BinaryColor::Off));
+
+    let mut display = MockDisplay::new();
+    display.set_allow_overdraw(true);
+    Text::with_baseline("i", Point::new(0, 10), style, Baseline::Bottom)
+        .draw(&mut display)
+        .unwrap();
+
+    display.assert_pattern(&[
+        "......", "......", "..#...", "......", ".##...", "..#...", "..#...", "..#...", "..##..",
+        "......", "......",
+    ]);
+}
+
+include!("generated/gohufont.rs");
+
+#[test]
+fn generate_gohufont_static_font() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+
+    assert_eq!(
+        bdf::Generator::new(&font, "GOHUFONT")
+            .subset("Hi,".chars())
+            .generate()
+            .unwrap(),
+        std::fs::read_to_string("tests/generated/gohufont.rs").unwrap()
+    );
+
+    assert_eq!(GOHUFONT.line_height(), 11);
+    assert_eq!(GOHUFONT.text_width("Hi,\nH"), 18);
+    assert_eq!(GOHUFONT.glyph('i').map(|g| g.advance), Some(6));
+    assert!(GOHUFONT.glyph('g').is_none());
+
+    let mut expected = Vec::new();
+    bdf::Renderer::new(&font).draw("Hi,\n,", 3, 4, &mut |x, y| expected.push((x, y)));
+
+    let mut pixels = Vec::new();
+    GOHUFONT.draw("Hi,\n,", 3, 4, &mut |x, y| pixels.push((x, y)));
+
+    expected.sort_unstable();
+    pixels.sort_unstable();
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn static_font_control_characters() {
+    static FONT: bdf::StaticFont = bdf::StaticFont {
+        name: "test",
+        ascent: 1,
+        descent: 0,
+        default: Some('?'),
+        glyphs: &[bdf::StaticGlyph {
+            codepoint: '?',
+            width: 1,
+            height: 1,
+            x: 0,
+            y: 0,
+            advance: 2,
+            bitmap: &[0x80],
+        }],
+    };
+
+    // Like `Layout`, control characters are skipped instead of drawing the
+    // default glyph.
+    assert_eq!(FONT.text_width("a\tb\r"), 4);
+
+    let mut pixels = Vec::new();
+    FONT.draw("a\tb\r", 0, 0, &mut |x, y| pixels.push((x, y)));
+    assert_eq!(pixels, [(0, 0), (2, 0)]);
+}
+
+/// Declarations of the Adafruit GFX and u8g2 headers the exported sources
+/// include.
+const GFX_HEADER: &str = "#include <stdint.h>
+#define PROGMEM
+typedef struct {
+  uint16_t bitmapOffset;
+  uint8_t width, height, xAdvance;
+  int8_t xOffset, yOffset;
+} GFXglyph;
+typedef struct {
+  uint8_t *bitmap;
+  GFXglyph *glyph;
+  uint16_t first, last;
+  uint8_t yAdvance;
+} GFXfont;
+";
+
+const U8G2_HEADER: &str = "#include <stdint.h>
+#define U8G2_FONT_SECTION(name)
+";
+
+/// Compiles the C source with the given headers and runs it.
+fn compile_and_run(name: &str, headers: &[(&str, &str)], source: &str) {
+    use std::process::Command;
+
+    let dir = std::env::temp_dir().join(format!("bdf-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for (header, contents) in headers {
+        std::fs::write(dir.join(header), contents).unwrap();
+    }
+
+    std::fs::write(dir.join("main.c"), source).unwrap();
+
+    // The sources are only compiled where a C compiler is around, the tables
+    // are checked from Rust anyway.
+    let status = match Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".into()))
+        .current_dir(&dir)
+        .args(["-std=c99", "-pedantic", "-Wall", "-Wextra", "-Werror"])
+        .args(["-I", ".", "-o", "main", "main.c"])
+        .status()
+    {
+        Ok(status) => status,
+
+        Err(error) => {
+            eprintln!("skipping the C check of {}: {}", name, error);
+            std::fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+    };
+    assert!(status.success());
+
+    let binary = dir
+        .join("main")
+        .with_extension(std::env::consts::EXE_EXTENSION);
+    let status = Command::new(binary).status().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+/// Gets the numbers in the initializer of the C array with the given name.
+fn array(source: &str, name: &str) -> Vec<i64> {
+    let start = source.find(&format!("{}[", name)).unwrap();
+    let body = &source[start..];
+    let body = &body[body.find('{').unwrap() + 1..body.find("};").unwrap()];
+
+    body.lines()
+        .map(|line| line.split("//").next().unwrap())
+        .flat_map(|line| line.split([',', '{', '}']))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| match value.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16).unwrap(),
+            None => value.parse().unwrap(),
+        })
+        .collect()
+}
+
+#[test]
+fn export_gohufont_gfx() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let source = bdf::Exporter::new(&font, "gohufont", bdf::Format::Gfx)
+        .range(' '..='~')
+        .generate()
+        .unwrap();
+
+    // The entries match the metrics of the glyphs, with the offsets following
+    // the sizes of the bitmaps packed without padding.
+    let bitmaps = array(&source, "gohufontBitmaps");
+    let entries = array(&source, "gohufontGlyphs");
+    let mut offset = 0;
+
+    assert_eq!(entries.len(), 95 * 6);
+
+    for (c, entry) in (' '..='~').zip(entries.chunks(6)) {
+        let glyph = &font.glyphs()[&c];
+        let bbx = glyph.bounds();
+        let size = (bbx.width * bbx.height + 7) / 8;
+
+        assert_eq!(
+            entry,
+            [
+                offset,
+                bbx.width as i64,
+                bbx.height as i64,
+                font.advance(glyph) as i64,
+                bbx.x as i64,
+                (1 - bbx.y - bbx.height as i32) as i64,
+            ],
+            "{:?}",
+            c
+        );
+
+        let bytes = bitmaps[offset as usize..][..size as usize]
+            .iter()
+            .map(|&byte| byte as u8)
+            .collect::<Vec<_>>();
+        let packing = bdf::Packing::new(bdf::Scan::Rows).padded(false);
+        assert_eq!(
+            &bdf::Bitmap::unpack(&bytes, bbx.width, bbx.height, packing).unwrap(),
+            glyph.map()
+        );
+
+        offset += size as i64;
+    }
+
+    assert_eq!(bitmaps.len() as i64, offset);
+
+    // Every glyph has its bits in the bitmaps, 'H' starting with its two
+    // stems.
+    compile_and_run(
+        "gfx",
+        &[("Adafruit_GFX.h", GFX_HEADER)],
+        &(source
+            + "
+int main(void) {
+  const GFXfont *font = &gohufont;
+  unsigned count = sizeof(gohufontGlyphs) / sizeof(GFXglyph);
+  unsigned i;
+  const GFXglyph *h = &font->glyph['H' - font->first];
+
+  if (font->first != ' ' || font->last != '~' || count != 95 || font->yAdvance != 11)
+    return 1;
+
+  for (i = 0; i < count; i++) {
+    const GFXglyph *glyph = &font->glyph[i];
+
+    if ((unsigned long)(glyph->bitmapOffset + (glyph->width * glyph->height + 7) / 8) > sizeof(gohufontBitmaps))
+      return 2;
+  }
+
+  return h->width == 5 && (font->bitmap[h->bitmapOffset] & 0xF8) == 0x88 ? 0 : 3;
+}
+"),
+    );
+}
+
+#[test]
+fn export_gohufont_u8g2() {
+    let font = bdf::open("tests/gohufont.bdf").unwrap();
+    let source = bdf::Exporter::new(&font, "gohufont", bdf::Format::U8g2)
+        .generate()
+        .unwrap();
+
+    // The metrics at the start of every glyph match the font, as bit fields
+    // of the sizes given in the header.
+    let data = array(&source, "gohufont")
+        .into_iter()
+        .map(|byte| byte as u8)
+        .collect::<Vec<_>>();
+    let mut offset = 23;
+
+    while data[offset + 1] != 0 {
+        let glyph = &font.glyphs()[&(data[offset] as char)];
+        let bbx = glyph.bounds();
+        let mut bit = (offset + 2) * 8;
+        let mut field = |count: u8, signed: bool| {
+            let mut value = 0;
+
+            for i in 0..count {
+                value |= ((data[bit / 8] >> (bit % 8)) as i32 & 1) << i;
+                bit += 1;
+            }
+
+            if signed {
+                value - (1 << (count - 1))
+            } else {
+                value
+            }
+        };
+
+        assert_eq!(
+            [
+                field(data[4], false),
+                field(data[5], false),
+                field(data[6], true),
+                field(data[7], true),
+                field(data[8], true),
+            ],
+            [
+                bbx.width as i32,
+                bbx.height as i32,
+                bbx.x,
+                bbx.y,
+                font.advance(glyph) as i32,
+            ]
+        );
+
+        offset += data[offset + 1] as usize;
+    }
+
+    assert_eq!(
+        data[0] as usize,
+        font.glyphs().keys().filter(|&&c| c <= '\u{FF}').count()
+    );
+
+    // Walk the glyphs the way u8g2 looks them up, by their sizes until the
+    // empty glyph, which must end where the header says.
+    compile_and_run(
+        "u8g2",
+        &[("u8g2.h", U8G2_HEADER)],
+        &(source
+            + "
+int main(void) {
+  const uint8_t *font = gohufont;
+  unsigned unicode = 23 + (font[21] << 8 | font[22]);
+  unsigned offset = 23;
+  unsigned count = 0;
+  int previous = -1;
+
+  while (font[offset + 1] != 0) {
+    if (font[offset] <= previous)
+      return 1;
+
+    previous = font[offset];
+    count++;
+    offset += font[offset + 1];
+
+    if (offset >= sizeof(gohufont))
+      return 2;
+  }
+
+  return count == font[0] && offset + 2 == unicode ? 0 : 3;
+}
+"),
+    );
+}